@@ -0,0 +1,3 @@
+mod sign_data;
+pub mod utils;
+pub use sign_data::CardanoSignData;
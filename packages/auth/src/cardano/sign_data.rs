@@ -0,0 +1,86 @@
+use {super::utils::{address_key_hash, parse_cose_key, parse_cose_sign1, sig_structure}, saa_common::ensure};
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, CryptoApi, Verifiable};
+
+
+/// Credential produced by CIP-30 `signData` following the message signing spec of CIP-8
+#[saa_schema::saa_type]
+pub struct CardanoSignData {
+    /// Hex encoded raw bytes of the signing address as returned by CIP-30 wallets
+    pub address:   String,
+    /// Payload passed to `signData` that must be covered by the signature
+    pub message:   Binary,
+    /// CBOR encoded `COSE_Sign1` returned as `signature` by the wallet
+    pub signature: Binary,
+    /// CBOR encoded `COSE_Key` returned as `key` by the wallet
+    pub key:       Binary,
+}
+
+
+
+impl CardanoSignData {
+    /// Check the decoded structures against the credential and return
+    /// the signed `Sig_structure`, the signature and the public key
+    fn signed_data(&self) -> Result<[Vec<u8>; 3], AuthError> {
+        let sign1 = parse_cose_sign1(&self.signature)?;
+        let pubkey = parse_cose_key(&self.key)?;
+
+        let address = hex::decode(&self.address)
+            .map_err(|e| AuthError::generic(e.to_string()))?;
+        ensure!(sign1.address == address, AuthError::generic("Address in protected headers doesn't match"));
+        ensure!(
+            address_key_hash(&address)? == saa_crypto::hashes::blake2b_224(&pubkey),
+            AuthError::RecoveryMismatch
+        );
+
+        let payload = if sign1.hashed {
+            saa_crypto::hashes::blake2b_224(&self.message).to_vec()
+        } else {
+            self.message.to_vec()
+        };
+        if let Some(signed) = sign1.payload.as_ref() {
+            ensure!(*signed == payload, AuthError::InvalidSignedData);
+        }
+        Ok([sig_structure(&sign1.protected, &payload), sign1.signature, pubkey])
+    }
+}
+
+
+
+impl Verifiable for CardanoSignData {
+
+    fn id(&self) -> CredentialId {
+        self.address.clone()
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        if self.signature.is_empty() || self.message.is_empty() || self.key.is_empty() {
+            return Err(AuthError::MissingData("Missing credential data".to_string()));
+        }
+        let address = hex::decode(&self.address)
+            .map_err(|e| AuthError::generic(e.to_string()))?;
+        if address.len() < 29 {
+            return Err(AuthError::MissingData("Address must be at least 29 bytes".to_string()));
+        }
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let [message, signature, pubkey] = self.signed_data()?;
+        let success = api.ed25519_verify(&message, &signature, &pubkey)?;
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+
+
+}
@@ -0,0 +1,119 @@
+use saa_common::{AuthError, Vec, ToString, ensure};
+use saa_crypto::cbor::{self, CborValue};
+
+
+/// COSE algorithm identifier of EdDSA
+pub const COSE_ALG_EDDSA : i128 = -8;
+/// COSE key type for Octet Key Pairs
+const COSE_KTY_OKP : i128 = 1;
+/// COSE curve identifier of Ed25519
+const COSE_CRV_ED25519 : i128 = 6;
+/// CBOR tag of a COSE_Sign1 structure
+const COSE_SIGN1_TAG : u64 = 18;
+
+
+
+/// Decoded `COSE_Sign1` structure as returned by CIP-30 `signData`
+pub struct CoseSign1 {
+    /// Serialized protected headers exactly as they were signed
+    pub protected   :   Vec<u8>,
+    /// Raw bytes of the address from the protected headers
+    pub address     :   Vec<u8>,
+    /// Whether the payload has been hashed with blake2b-224 before signing
+    pub hashed      :   bool,
+    /// Embedded payload. `None` when the payload is detached
+    pub payload     :   Option<Vec<u8>>,
+    /// Ed25519 signature over the `Sig_structure`
+    pub signature   :   Vec<u8>,
+}
+
+
+
+pub fn parse_cose_sign1(data: &[u8]) -> Result<CoseSign1, AuthError> {
+    let value = cbor::from_slice(data)?;
+    let value = match value {
+        CborValue::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        CborValue::Tag(..) => return Err(AuthError::generic("Unexpected tag of COSE_Sign1")),
+        other => other,
+    };
+    let items = value.as_array()
+        .ok_or_else(|| AuthError::generic("COSE_Sign1 must be an array"))?;
+    ensure!(items.len() == 4, AuthError::generic("COSE_Sign1 must have 4 elements"));
+
+    let protected = items[0].as_bytes()
+        .ok_or_else(|| AuthError::generic("Protected headers must be a byte string"))?
+        .to_vec();
+    let headers = cbor::from_slice(&protected)?;
+
+    ensure!(
+        headers.get_int(1).and_then(|a| a.as_int()) == Some(COSE_ALG_EDDSA),
+        AuthError::generic("Only EdDSA algorithm is supported")
+    );
+    let address = headers.get_text("address")
+        .and_then(|a| a.as_bytes())
+        .ok_or_else(|| AuthError::MissingData("Missing address in protected headers".to_string()))?
+        .to_vec();
+
+    let hashed = items[1].get_text("hashed")
+        .and_then(|h| h.as_bool())
+        .unwrap_or(false);
+
+    let payload = match &items[2] {
+        CborValue::Bytes(b) => Some(b.clone()),
+        CborValue::Null => None,
+        _ => return Err(AuthError::generic("Payload must be a byte string or null")),
+    };
+
+    let signature = items[3].as_bytes()
+        .ok_or_else(|| AuthError::generic("Signature must be a byte string"))?
+        .to_vec();
+
+    Ok(CoseSign1 { protected, address, hashed, payload, signature })
+}
+
+
+
+/// Extract an Ed25519 public key from a CBOR encoded `COSE_Key`
+pub fn parse_cose_key(data: &[u8]) -> Result<Vec<u8>, AuthError> {
+    let key = cbor::from_slice(data)?;
+    ensure!(
+        key.get_int(1).and_then(|k| k.as_int()) == Some(COSE_KTY_OKP),
+        AuthError::generic("COSE_Key must be of OKP type")
+    );
+    if let Some(alg) = key.get_int(3) {
+        ensure!(alg.as_int() == Some(COSE_ALG_EDDSA), AuthError::generic("COSE_Key must use EdDSA"));
+    }
+    ensure!(
+        key.get_int(-1).and_then(|c| c.as_int()) == Some(COSE_CRV_ED25519),
+        AuthError::generic("COSE_Key must be on Ed25519 curve")
+    );
+    let pubkey = key.get_int(-2)
+        .and_then(|x| x.as_bytes())
+        .ok_or_else(|| AuthError::MissingData("Missing public key in COSE_Key".to_string()))?;
+    ensure!(pubkey.len() == 32, AuthError::InvalidLength("Ed25519 public key".to_string(), 32, pubkey.len() as u16));
+    Ok(pubkey.to_vec())
+}
+
+
+
+/// Serialized `Sig_structure` of COSE_Sign1 with empty external data
+pub fn sig_structure(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    cbor::to_vec(&CborValue::Array(saa_common::vec![
+        CborValue::Text("Signature1".to_string()),
+        CborValue::Bytes(protected.to_vec()),
+        CborValue::Bytes(Vec::new()),
+        CborValue::Bytes(payload.to_vec()),
+    ]))
+}
+
+
+
+/// Key hash that an address commits to. Only addresses with a key based payment part
+/// (base, pointer and enterprise) and key based reward addresses are supported
+pub fn address_key_hash(address: &[u8]) -> Result<&[u8], AuthError> {
+    ensure!(address.len() >= 29, AuthError::generic("Invalid Cardano address length"));
+    match address[0] >> 4 {
+        0 | 2 | 4 | 6 | 14 => Ok(&address[1..29]),
+        _ => Err(AuthError::generic("Only key based Cardano addresses are supported")),
+    }
+}
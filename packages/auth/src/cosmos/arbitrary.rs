@@ -1,5 +1,4 @@
-
-use saa_common::{AuthError, Binary, CredentialId, String, ToString, Verifiable};
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, CryptoApi, Verifiable};
 
 
 #[saa_schema::saa_type]
@@ -12,8 +11,8 @@ pub struct CosmosArbitrary {
 }
 
 
-#[cfg(any(feature = "cosmwasm", feature = "native"))]
 impl CosmosArbitrary {
+    #[cfg(any(feature = "cosmwasm", feature = "native"))]
     fn message_digest(&self) -> Result<Vec<u8>, AuthError> {
         use saa_crypto::{hashes::sha256, pubkey_to_address};
         match self.hrp {
@@ -28,6 +27,11 @@ impl CosmosArbitrary {
             None => Err(AuthError::Generic("Must provide prefix for the public key".to_string()))
         }
     }
+
+    #[cfg(not(any(feature = "cosmwasm", feature = "native")))]
+    fn message_digest(&self) -> Result<Vec<u8>, AuthError> {
+        Err(AuthError::generic("Address of the signer can only be derived with `cosmwasm` or `native` feature"))
+    }
 }
 
 
@@ -50,9 +54,8 @@ impl Verifiable for CosmosArbitrary {
         Ok(())
     }
 
-    #[cfg(feature = "native")]
-    fn verify(&self) -> Result<(), AuthError> {
-        let success = saa_crypto::secp256k1_verify(
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let success = api.secp256k1_verify(
             &self.message_digest()?,
             &self.signature,
             &self.pubkey
@@ -61,19 +64,15 @@ impl Verifiable for CosmosArbitrary {
         Ok(())
     }
 
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
 
     #[cfg(feature = "cosmwasm")]
-    fn verify_cosmwasm(
-        &self, 
-        api:  &dyn saa_common::wasm::Api
-    ) -> Result<(), AuthError> {
-        let success = api.secp256k1_verify(
-            &self.message_digest()?,
-            &self.signature,
-            &self.pubkey
-        )?;
-        saa_common::ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
-        Ok(())
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 
 }
\ No newline at end of file
@@ -1,7 +1,5 @@
-
-#[cfg(any(feature = "cosmwasm", feature = "native"))]
-use {super::utils::{get_recovery_param, preamble_msg_eth}, saa_common::ensure};
-use saa_common::{CredentialId, AuthError, Binary, String, ToString, Verifiable };
+use {super::utils::{recover_address_bytes, preamble_msg_eth}, saa_common::ensure};
+use saa_common::{CredentialId, AuthError, Binary, String, ToString, CryptoApi, Verifiable };
 
 
 #[saa_schema::saa_type]
@@ -37,46 +35,30 @@ impl Verifiable for EthPersonalSign {
     }
 
 
-    #[cfg(feature = "native")] 
-    fn verify(&self) -> Result<(), AuthError> {
-        let signature = &self.signature.to_vec();
-        let key_data = saa_crypto::secp256k1_recover_pubkey(
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let recovered = recover_address_bytes(
             &preamble_msg_eth(&self.message), 
-            &signature[..64], 
-            get_recovery_param(signature[64])?
+            &self.signature,
+            |h, s, v| api.secp256k1_recover_pubkey(h, s, v)
         )?;
-        let hash = saa_crypto::hashes::keccak256(&key_data[1..]);
-
         let addr_bytes = hex::decode(&self.signer[2..])
-        .map_err(|e| AuthError::generic(e.to_string()))?;
+            .map_err(|e| AuthError::generic(e.to_string()))?;
     
-        ensure!(addr_bytes == hash[12..], AuthError::RecoveryMismatch);
+        ensure!(addr_bytes == recovered, AuthError::RecoveryMismatch);
         
         Ok(())
     }
 
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
 
     #[cfg(feature = "cosmwasm")]
     fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
-        
-        let signature = &self.signature.to_vec();
-        
-        let key_data = api.secp256k1_recover_pubkey(
-            &preamble_msg_eth(&self.message), 
-            &signature[..64], 
-            get_recovery_param(signature[64])?
-        )?;
-    
-        let hash = saa_crypto::hashes::keccak256(&key_data[1..]);
-
-        let addr_bytes = hex::decode(&self.signer[2..])
-            .map_err(|e| AuthError::generic(e.to_string()))?;
-        
-        ensure!(addr_bytes == hash[12..], AuthError::RecoveryMismatch);
-
-        Ok(())
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 
-
 }
 
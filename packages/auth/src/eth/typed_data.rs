@@ -1,7 +1,5 @@
-
-#[cfg(any(feature = "cosmwasm", feature = "native"))]
 use {super::utils::{get_recovery_param, preamble_msg_eth}, saa_common::ensure};
-use saa_common::{AuthError, Binary, CredentialId, String, ToString, Uint64, Verifiable };
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, Uint64, CryptoApi, Verifiable };
 use saa_schema::saa_type;
 
 // TODO: remove (crate) restrictions once ready
@@ -75,10 +73,9 @@ impl Verifiable for EthTypedData {
     }
 
 
-    #[cfg(feature = "native")] 
-    fn verify(&self) -> Result<(), AuthError> {
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
         let signature = &self.signature.to_vec();
-        let key_data = saa_crypto::secp256k1_recover_pubkey(
+        let key_data = api.secp256k1_recover_pubkey(
             &preamble_msg_eth(&self.message), 
             &signature[..64], 
             get_recovery_param(signature[64])?
@@ -93,28 +90,16 @@ impl Verifiable for EthTypedData {
         Ok(())
     }
 
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
 
     #[cfg(feature = "cosmwasm")]
     fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
-        
-        let signature = &self.signature.to_vec();
-        
-        let key_data = api.secp256k1_recover_pubkey(
-            &preamble_msg_eth(&self.message), 
-            &signature[..64], 
-            get_recovery_param(signature[64])?
-        )?;
-    
-        let hash = saa_crypto::hashes::keccak256(&key_data[1..]);
-
-        let addr_bytes = hex::decode(&self.signer[2..])
-            .map_err(|e| AuthError::generic(e.to_string()))?;
-        
-        ensure!(addr_bytes == hash[12..], AuthError::RecoveryMismatch);
-
-        Ok(())
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 
-
 }
 
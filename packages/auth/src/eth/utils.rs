@@ -1,4 +1,4 @@
-use saa_common::{AuthError, vec, format};
+use saa_common::{AuthError, ToString, Vec, vec, format, ensure};
 use saa_crypto::hashes::keccak256;
 
 
@@ -21,3 +21,24 @@ pub fn get_recovery_param(v: u8) -> Result<u8, AuthError> {
         _ => Err(AuthError::RecoveryParam)
     }
 }
+
+
+
+/// Recover a public key from a 65 bytes signature with `v` as the last byte and
+/// derive the 20 bytes of the account from it. The recovery itself is delegated
+/// to the passed function to support both native and API backed environments
+pub fn recover_address_bytes<F>(
+    hash: &[u8], 
+    signature: &[u8], 
+    recover: F
+) -> Result<[u8; 20], AuthError> 
+    where F: FnOnce(&[u8], &[u8], u8) -> Result<Vec<u8>, AuthError>
+{
+    ensure!(
+        signature.len() == 65,
+        AuthError::InvalidLength("Recoverable signature".to_string(), 65, signature.len() as u16)
+    );
+    let key_data = recover(hash, &signature[..64], get_recovery_param(signature[64])?)?;
+    let hash = keccak256(&key_data[1..]);
+    Ok(hash[12..].try_into().unwrap())
+}
\ No newline at end of file
@@ -1,4 +1,14 @@
-#[cfg(any(feature = "eth_personal", feature = "eth_typed_data"))]
+#[cfg(any(feature = "eth_personal", feature = "eth_typed_data", feature = "tron"))]
 pub mod eth;
 #[cfg(feature = "cosmos")]
-pub mod cosmos;
\ No newline at end of file
+pub mod cosmos;
+#[cfg(feature = "cardano")]
+pub mod cardano;
+#[cfg(feature = "tron")]
+pub mod tron;
+#[cfg(feature = "ton")]
+pub mod ton;
+#[cfg(feature = "tezos")]
+pub mod tezos;
+#[cfg(feature = "stellar")]
+pub mod stellar;
\ No newline at end of file
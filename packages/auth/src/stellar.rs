@@ -0,0 +1,3 @@
+mod message;
+pub mod utils;
+pub use message::StellarSignMessage;
@@ -0,0 +1,56 @@
+use {super::utils::preamble_msg_stellar, saa_common::ensure};
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, CryptoApi, Verifiable};
+use super::utils::decode_account_id;
+
+
+/// Message signed by Stellar wallets such as Freighter according to SEP-53
+#[saa_schema::saa_type]
+pub struct StellarSignMessage {
+    pub message:   Binary,
+    pub signature: Binary,
+    /// StrKey encoded `G...` account id of the signer
+    pub signer:    String,
+}
+
+
+
+impl Verifiable for StellarSignMessage {
+
+    fn id(&self) -> CredentialId {
+        self.signer.clone()
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        if self.message.is_empty() {
+            return Err(AuthError::MissingData("Empty message".to_string()));
+        }
+        if self.signature.len() != 64 {
+            return Err(AuthError::InvalidLength("Signature".to_string(), 64, self.signature.len() as u16));
+        }
+        decode_account_id(&self.signer)?;
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let success = api.ed25519_verify(
+            &preamble_msg_stellar(&self.message),
+            &self.signature,
+            &decode_account_id(&self.signer)?
+        )?;
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+
+
+}
@@ -0,0 +1,93 @@
+use saa_common::{AuthError, String, ToString, Vec, ensure};
+use saa_crypto::hashes::sha256;
+
+
+/// Version byte of StrKey encoded ed25519 account ids (`G...`)
+pub const ACCOUNT_ID_VERSION : u8 = 6 << 3;
+
+const BASE32_ALPHABET : &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+
+
+/// CRC16-XModem checksum used by StrKey
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc : u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+
+fn base32_decode(data: &str) -> Result<Vec<u8>, AuthError> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in data.bytes() {
+        let value = BASE32_ALPHABET.iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| AuthError::generic("Invalid base32 character in StrKey"))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    ensure!(buffer == 0, AuthError::generic("Non canonical base32 encoding of StrKey"));
+    Ok(out)
+}
+
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+
+
+/// Decode a `G...` StrKey account id into the raw ed25519 public key
+pub fn decode_account_id(address: &str) -> Result<[u8; 32], AuthError> {
+    let decoded = base32_decode(address)?;
+    ensure!(decoded.len() == 35, AuthError::InvalidLength("Stellar account id".to_string(), 35, decoded.len() as u16));
+    ensure!(decoded[0] == ACCOUNT_ID_VERSION, AuthError::generic("Only `G...` account ids are supported"));
+
+    let checksum = crc16_xmodem(&decoded[..33]).to_le_bytes();
+    ensure!(checksum == decoded[33..], AuthError::generic("Invalid StrKey checksum"));
+
+    Ok(decoded[1..33].try_into().unwrap())
+}
+
+
+/// Encode a raw ed25519 public key into a `G...` StrKey account id
+pub fn encode_account_id(pubkey: &[u8; 32]) -> String {
+    let mut data = Vec::with_capacity(35);
+    data.push(ACCOUNT_ID_VERSION);
+    data.extend_from_slice(pubkey);
+    data.extend_from_slice(&crc16_xmodem(&data).to_le_bytes());
+    base32_encode(&data)
+}
+
+
+
+/// Digest of a message signed according to SEP-53
+pub fn preamble_msg_stellar(msg: &[u8]) -> Vec<u8> {
+    const PREFIX: &str = "Stellar Signed Message:\n";
+    sha256(&[PREFIX.as_bytes(), msg].concat())
+}
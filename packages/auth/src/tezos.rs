@@ -0,0 +1,3 @@
+mod payload;
+pub mod utils;
+pub use payload::TezosSignPayload;
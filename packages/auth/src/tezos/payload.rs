@@ -0,0 +1,79 @@
+use {super::utils::message_digest, saa_common::ensure};
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, CryptoApi, Verifiable};
+use super::utils::{TezosCurve, decode_address, decode_public_key, pubkey_to_address, validate_micheline_string};
+
+
+/// Payload signed with `signPayload` of Tezos wallets (Temple, Kukai, Beacon) 
+/// using `MICHELINE` signing type. The message is packed as a Micheline string
+/// and hashed with `blake2b-256` before being signed
+#[saa_schema::saa_type]
+pub struct TezosSignPayload {
+    /// Message string without the Micheline encoding
+    pub message:   Binary,
+    /// Raw 64 bytes of the signature. Use `utils::decode_signature` for base58 encoded ones
+    pub signature: Binary,
+    /// Base58check encoded `edpk`, `sppk` or `p2pk` public key
+    pub pubkey:    String,
+    /// `tz1`, `tz2` or `tz3` address of the signer
+    pub signer:    String,
+}
+
+
+
+impl TezosSignPayload {
+    /// Decode the public key and make sure that it belongs to the signer
+    pub fn checked_pubkey(&self) -> Result<(TezosCurve, Vec<u8>), AuthError> {
+        let (addr_curve, _) = decode_address(&self.signer)?;
+        let (key_curve, key) = decode_public_key(&self.pubkey)?;
+        if addr_curve != key_curve || pubkey_to_address(key_curve, &key) != self.signer {
+            return Err(AuthError::RecoveryMismatch);
+        }
+        Ok((key_curve, key))
+    }
+}
+
+
+
+impl Verifiable for TezosSignPayload {
+
+    fn id(&self) -> CredentialId {
+        self.signer.clone()
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        if self.message.is_empty() {
+            return Err(AuthError::MissingData("Empty message".to_string()));
+        }
+        if self.signature.len() != 64 {
+            return Err(AuthError::InvalidLength("Signature".to_string(), 64, self.signature.len() as u16));
+        }
+        validate_micheline_string(&self.message)?;
+        self.checked_pubkey()?;
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let (curve, key) = self.checked_pubkey()?;
+        let digest = message_digest(&self.message);
+        let success = match curve {
+            TezosCurve::Ed25519 => api.ed25519_verify(&digest, &self.signature, &key)?,
+            TezosCurve::Secp256k1 => api.secp256k1_verify(&digest, &self.signature, &key)?,
+            TezosCurve::P256 => api.secp256r1_verify(&digest, &self.signature, &key)?,
+        };
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+
+
+}
@@ -0,0 +1,185 @@
+use saa_common::{AuthError, String, ToString, Vec, vec, ensure};
+use saa_crypto::hashes::{blake2b_160, blake2b_256, sha256};
+
+
+/// Curves supported by Tezos accounts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TezosCurve {
+    /// `tz1` addresses and `edpk` keys
+    Ed25519,
+    /// `tz2` addresses and `sppk` keys
+    Secp256k1,
+    /// `tz3` addresses and `p2pk` keys
+    P256,
+}
+
+
+const TZ1_PREFIX : [u8; 3] = [6, 161, 159];
+const TZ2_PREFIX : [u8; 3] = [6, 161, 161];
+const TZ3_PREFIX : [u8; 3] = [6, 161, 164];
+
+const EDPK_PREFIX : [u8; 4] = [13, 15, 37, 217];
+const SPPK_PREFIX : [u8; 4] = [3, 254, 226, 86];
+const P2PK_PREFIX : [u8; 4] = [3, 178, 139, 127];
+
+const EDSIG_PREFIX : [u8; 5] = [9, 245, 205, 134, 18];
+const SPSIG_PREFIX : [u8; 5] = [13, 115, 101, 19, 63];
+const P2SIG_PREFIX : [u8; 4] = [54, 240, 44, 52];
+const SIG_PREFIX   : [u8; 3] = [4, 130, 43];
+
+/// Watermark byte of Micheline expressions packed with `PACK`
+const MICHELINE_WATERMARK : u8 = 0x05;
+/// Tag of a Micheline string node
+const MICHELINE_STRING_TAG : u8 = 0x01;
+
+
+
+impl TezosCurve {
+
+    fn address_prefix(&self) -> &'static [u8] {
+        match self {
+            TezosCurve::Ed25519 => &TZ1_PREFIX,
+            TezosCurve::Secp256k1 => &TZ2_PREFIX,
+            TezosCurve::P256 => &TZ3_PREFIX,
+        }
+    }
+
+    fn pubkey_prefix(&self) -> &'static [u8] {
+        match self {
+            TezosCurve::Ed25519 => &EDPK_PREFIX,
+            TezosCurve::Secp256k1 => &SPPK_PREFIX,
+            TezosCurve::P256 => &P2PK_PREFIX,
+        }
+    }
+
+    fn pubkey_len(&self) -> usize {
+        match self {
+            TezosCurve::Ed25519 => 32,
+            _ => 33,
+        }
+    }
+}
+
+
+
+fn b58check_decode(data: &str) -> Result<Vec<u8>, AuthError> {
+    let decoded = bs58::decode(data)
+        .into_vec()
+        .map_err(|e| AuthError::generic(e.to_string()))?;
+    ensure!(decoded.len() > 4, AuthError::generic("Base58check data is too short"));
+    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+    ensure!(sha256(&sha256(payload))[..4] == *checksum, AuthError::generic("Invalid base58check checksum"));
+    Ok(payload.to_vec())
+}
+
+
+fn b58check_encode(prefix: &[u8], data: &[u8]) -> String {
+    let mut payload = [prefix, data].concat();
+    let checksum = sha256(&sha256(&payload));
+    payload.extend_from_slice(&checksum[..4]);
+    bs58::encode(payload).into_string()
+}
+
+
+
+/// Decode a `tz1`, `tz2` or `tz3` address into its curve and the 20 bytes of the key hash
+pub fn decode_address(address: &str) -> Result<(TezosCurve, [u8; 20]), AuthError> {
+    let decoded = b58check_decode(address)?;
+    ensure!(decoded.len() == 23, AuthError::InvalidLength("Tezos address".to_string(), 23, decoded.len() as u16));
+    let curve = match <[u8; 3]>::try_from(&decoded[..3]).unwrap() {
+        TZ1_PREFIX => TezosCurve::Ed25519,
+        TZ2_PREFIX => TezosCurve::Secp256k1,
+        TZ3_PREFIX => TezosCurve::P256,
+        _ => return Err(AuthError::generic("Only tz1, tz2 and tz3 addresses are supported")),
+    };
+    Ok((curve, decoded[3..].try_into().unwrap()))
+}
+
+
+/// Decode an `edpk`, `sppk` or `p2pk` public key into its curve and raw bytes
+pub fn decode_public_key(pubkey: &str) -> Result<(TezosCurve, Vec<u8>), AuthError> {
+    let decoded = b58check_decode(pubkey)?;
+    ensure!(decoded.len() > 4, AuthError::generic("Invalid Tezos public key"));
+    let curve = match <[u8; 4]>::try_from(&decoded[..4]).unwrap() {
+        EDPK_PREFIX => TezosCurve::Ed25519,
+        SPPK_PREFIX => TezosCurve::Secp256k1,
+        P2PK_PREFIX => TezosCurve::P256,
+        _ => return Err(AuthError::generic("Only edpk, sppk and p2pk public keys are supported")),
+    };
+    let key = decoded[4..].to_vec();
+    ensure!(
+        key.len() == curve.pubkey_len(),
+        AuthError::InvalidLength("Tezos public key".to_string(), curve.pubkey_len() as u16, key.len() as u16)
+    );
+    Ok((curve, key))
+}
+
+
+/// Decode a base58 encoded signature of any supported curve or a generic `sig` into raw 64 bytes
+pub fn decode_signature(signature: &str) -> Result<Vec<u8>, AuthError> {
+    let decoded = b58check_decode(signature)?;
+    let raw = if decoded.starts_with(&EDSIG_PREFIX) || decoded.starts_with(&SPSIG_PREFIX) {
+        &decoded[5..]
+    } else if decoded.starts_with(&P2SIG_PREFIX) {
+        &decoded[4..]
+    } else if decoded.starts_with(&SIG_PREFIX) {
+        &decoded[3..]
+    } else {
+        return Err(AuthError::generic("Unknown Tezos signature prefix"));
+    };
+    ensure!(raw.len() == 64, AuthError::InvalidLength("Tezos signature".to_string(), 64, raw.len() as u16));
+    Ok(raw.to_vec())
+}
+
+
+/// Encode raw public key bytes of the given curve into a base58check string
+pub fn encode_public_key(curve: TezosCurve, key: &[u8]) -> String {
+    b58check_encode(curve.pubkey_prefix(), key)
+}
+
+
+/// Derive the address of a public key: base58check of `blake2b-160` of the raw key
+pub fn pubkey_to_address(curve: TezosCurve, key: &[u8]) -> String {
+    b58check_encode(curve.address_prefix(), &blake2b_160(key))
+}
+
+
+
+/// Check that the message can be a Micheline string which is restricted
+/// to printable ASCII characters and line feeds
+pub fn validate_micheline_string(msg: &[u8]) -> Result<(), AuthError> {
+    ensure!(
+        msg.iter().all(|b| (0x20..=0x7e).contains(b) || *b == b'\n'),
+        AuthError::generic("Micheline strings may only contain printable ASCII characters")
+    );
+    Ok(())
+}
+
+
+/// Pack a string the same way as `PACK` instruction and wallets do for
+/// `MICHELINE` payloads: `0x05 0x01 <u32 length> <bytes>`
+pub fn pack_string(msg: &[u8]) -> Vec<u8> {
+    let mut packed = vec![MICHELINE_WATERMARK, MICHELINE_STRING_TAG];
+    packed.extend_from_slice(&(msg.len() as u32).to_be_bytes());
+    packed.extend_from_slice(msg);
+    packed
+}
+
+
+/// Inverse of `pack_string` that checks the encoding of the payload
+pub fn unpack_string(payload: &[u8]) -> Result<Vec<u8>, AuthError> {
+    ensure!(
+        payload.len() >= 6 && payload[0] == MICHELINE_WATERMARK && payload[1] == MICHELINE_STRING_TAG,
+        AuthError::generic("Payload must be a packed Micheline string")
+    );
+    let len = u32::from_be_bytes(payload[2..6].try_into().unwrap()) as usize;
+    ensure!(payload.len() - 6 == len, AuthError::generic("Invalid length of the packed Micheline string"));
+    validate_micheline_string(&payload[6..])?;
+    Ok(payload[6..].to_vec())
+}
+
+
+/// Digest signed by the wallets: `blake2b-256` of the packed payload
+pub fn message_digest(msg: &[u8]) -> [u8; 32] {
+    blake2b_256(&pack_string(msg))
+}
@@ -0,0 +1,3 @@
+mod proof;
+pub mod utils;
+pub use proof::{TonProof, TonProofChecks, TonProofInfo, TonProofPayload};
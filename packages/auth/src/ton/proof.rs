@@ -0,0 +1,151 @@
+use saa_common::ensure;
+use saa_common::{AuthError, Binary, CredentialId, String, ToString, Vec, CryptoApi, Verifiable};
+use saa_schema::saa_type;
+
+
+/// Proof of address ownership returned by TON Connect wallets for `ton_proof` item.
+/// The binding between the public key and the address isn't derivable from the proof itself
+/// and must be established when the credential is registered (e.g. from the wallet state init)
+#[saa_type]
+pub struct TonProof {
+    /// Raw address of the wallet in `<workchain>:<hex hash>` form
+    pub address:   String,
+    /// Ed25519 public key of the wallet
+    pub pubkey:    Binary,
+    /// Domain of the app that requested the proof
+    pub domain:    String,
+    /// Unix timestamp in seconds when the proof was signed
+    pub timestamp: u64,
+    /// Payload of the proof
+    pub message:   Binary,
+    pub signature: Binary,
+    /// Allowed domains and the current time to check the proof against. Can be supplied on
+    /// the backend / contract side and omitted by client. Must be set when going through the verification process
+    pub checks:    Option<TonProofChecks>,
+}
+
+
+
+/// Requirements that the domain and the timestamp of a proof must satisfy
+#[saa_type]
+pub struct TonProofChecks {
+    /// Domains of the apps that may request proofs
+    pub domains:   Vec<String>,
+    /// Maximum difference in seconds between the timestamp of a proof and the current time
+    pub max_age:   u64,
+    /// Current unix timestamp in seconds e.g. the block time
+    pub now:       u64,
+}
+
+
+
+/// Information stored together with a TON credential
+#[saa_type]
+pub struct TonProofInfo {
+    /// Ed25519 public key of the wallet
+    pub pubkey:    Binary,
+    /// Domains of the apps that may request proofs
+    pub domains:   Vec<String>,
+    /// Maximum difference in seconds between the timestamp of a proof and the current time
+    pub max_age:   u64,
+}
+
+
+
+/// Fields of the proof that are sent with a signed message and aren't stored with a credential
+#[saa_type]
+pub struct TonProofPayload {
+    /// Domain of the app that requested the proof
+    pub domain:    String,
+    /// Unix timestamp in seconds when the proof was signed
+    pub timestamp: u64,
+}
+
+
+
+impl TonProof {
+
+    fn message_digest(&self) -> Result<Vec<u8>, AuthError> {
+        super::utils::proof_digest(&self.address, &self.domain, self.timestamp, &self.message)
+    }
+
+    /// Check the domain and the timestamp of the proof against the required values
+    pub fn check_domain_and_time(&self) -> Result<(), AuthError> {
+        let checks = self.checks.as_ref()
+            .ok_or_else(|| AuthError::MissingData("Missing allowed domains of ton_proof".to_string()))?;
+        super::utils::check_domain_and_time(&self.domain, self.timestamp, &checks.domains, checks.now, checks.max_age)
+    }
+
+    /// Information to store with the credential. Only the domain of the proof is allowed
+    /// and the default max age is used when no requirements are set
+    pub fn info(&self) -> TonProofInfo {
+        let (domains, max_age) = match self.checks.as_ref() {
+            Some(checks) => (checks.domains.clone(), checks.max_age),
+            None => (saa_common::vec![self.domain.clone()], super::utils::DEFAULT_PROOF_MAX_AGE),
+        };
+        TonProofInfo { pubkey: self.pubkey.clone(), domains, max_age }
+    }
+}
+
+
+impl TonProofInfo {
+
+    /// Requirements for proofs of the stored credential at the given time
+    pub fn checks(&self, now: u64) -> TonProofChecks {
+        TonProofChecks {
+            domains: self.domains.clone(),
+            max_age: self.max_age,
+            now,
+        }
+    }
+
+    /// Same as `checks` but using the block time of the environment
+    #[cfg(feature = "cosmwasm")]
+    pub fn checks_env(&self, env: &saa_common::wasm::Env) -> TonProofChecks {
+        self.checks(env.block.time.seconds())
+    }
+}
+
+
+
+impl Verifiable for TonProof {
+
+    fn id(&self) -> CredentialId {
+        self.address.clone()
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        if self.signature.is_empty() || self.message.is_empty() || self.domain.is_empty() {
+            return Err(AuthError::MissingData("Missing credential data".to_string()));
+        }
+        if self.pubkey.len() != 32 {
+            return Err(AuthError::InvalidLength("Ed25519 public key".to_string(), 32, self.pubkey.len() as u16));
+        }
+        super::utils::parse_raw_address(&self.address)?;
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        self.check_domain_and_time()?;
+        let success = api.ed25519_verify(
+            &self.message_digest()?,
+            &self.signature,
+            &self.pubkey
+        )?;
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+
+
+}
@@ -0,0 +1,80 @@
+use saa_common::{AuthError, String, ToString, Vec, ensure};
+use saa_crypto::hashes::sha256;
+
+
+/// Maximum age in seconds of proofs for credentials registered without explicit requirements
+pub const DEFAULT_PROOF_MAX_AGE : u64 = 900;
+
+
+
+/// Decode a raw `<workchain>:<hex hash>` TON address
+pub fn parse_raw_address(address: &str) -> Result<(i32, [u8; 32]), AuthError> {
+    let (workchain, hash) = address.split_once(':')
+        .ok_or_else(|| AuthError::generic("TON address must be in raw `workchain:hash` form"))?;
+    let workchain = workchain.parse::<i32>()
+        .map_err(|e| AuthError::generic(e.to_string()))?;
+    let hash = hex::decode(hash)
+        .map_err(|e| AuthError::generic(e.to_string()))?;
+    ensure!(hash.len() == 32, AuthError::InvalidLength("TON address hash".to_string(), 32, hash.len() as u16));
+    Ok((workchain, hash.try_into().unwrap()))
+}
+
+
+
+/// Message of `ton_proof` as defined by TON Connect before it gets hashed
+pub fn proof_message(
+    address: &str,
+    domain: &str,
+    timestamp: u64,
+    payload: &[u8],
+) -> Result<Vec<u8>, AuthError> {
+    let (workchain, hash) = parse_raw_address(address)?;
+    let mut msg = Vec::with_capacity(18 + 4 + 32 + 4 + domain.len() + 8 + payload.len());
+    msg.extend_from_slice(b"ton-proof-item-v2/");
+    msg.extend_from_slice(&workchain.to_be_bytes());
+    msg.extend_from_slice(&hash);
+    msg.extend_from_slice(&(domain.len() as u32).to_le_bytes());
+    msg.extend_from_slice(domain.as_bytes());
+    msg.extend_from_slice(&timestamp.to_le_bytes());
+    msg.extend_from_slice(payload);
+    Ok(msg)
+}
+
+
+
+/// Digest that is signed by the wallet: `sha256(0xffff ++ "ton-connect" ++ sha256(message))`
+pub fn proof_digest(
+    address: &str,
+    domain: &str,
+    timestamp: u64,
+    payload: &[u8],
+) -> Result<Vec<u8>, AuthError> {
+    let message = proof_message(address, domain, timestamp, payload)?;
+    let mut full = Vec::with_capacity(2 + 11 + 32);
+    full.extend_from_slice(&[0xff, 0xff]);
+    full.extend_from_slice(b"ton-connect");
+    full.extend_from_slice(&sha256(&message));
+    Ok(sha256(&full))
+}
+
+
+
+/// Check that the proof has been issued for one of the allowed domains
+/// and that its timestamp is within `max_age` seconds from the current time
+pub fn check_domain_and_time(
+    domain: &str,
+    timestamp: u64,
+    allowed_domains: &[String],
+    now: u64,
+    max_age: u64,
+) -> Result<(), AuthError> {
+    ensure!(
+        allowed_domains.iter().any(|d| d == domain),
+        AuthError::Unauthorized(saa_common::format!("domain {} is not allowed", domain))
+    );
+    ensure!(
+        now.abs_diff(timestamp) <= max_age,
+        AuthError::Unauthorized("ton_proof timestamp is expired or too far in the future".to_string())
+    );
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod personal;
+pub mod utils;
+pub use personal::TronSignMessage;
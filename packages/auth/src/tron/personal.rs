@@ -0,0 +1,54 @@
+use {super::utils::preamble_msg_tron, crate::eth::utils::recover_address_bytes, saa_common::ensure};
+use saa_common::{CredentialId, AuthError, Binary, String, ToString, CryptoApi, Verifiable };
+
+
+/// Message signed with `signMessageV2` of TronWeb / TronLink
+#[saa_schema::saa_type]
+pub struct TronSignMessage {
+    pub message:   Binary,
+    pub signature: Binary,
+    /// Base58check encoded `T...` address of the signer
+    pub signer:    String,
+}
+
+
+impl Verifiable for TronSignMessage {
+
+    fn id(&self) -> CredentialId {
+        self.signer.to_string()
+    }
+
+
+    fn validate(&self) -> Result<(), AuthError> {
+        if self.signature.len() < 65 {
+            return Err(AuthError::MissingData("Signature must be at least 65 bytes".to_string()));
+        }
+        super::utils::address_to_bytes(&self.signer)?;
+        Ok(())
+    }
+
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let recovered = recover_address_bytes(
+            &preamble_msg_tron(&self.message), 
+            &self.signature,
+            |h, s, v| api.secp256k1_recover_pubkey(h, s, v)
+        )?;
+        let addr_bytes = super::utils::address_to_bytes(&self.signer)?;
+        ensure!(addr_bytes == recovered, AuthError::RecoveryMismatch);
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+
+
+}
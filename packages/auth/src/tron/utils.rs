@@ -0,0 +1,45 @@
+use saa_common::{AuthError, String, ToString, vec, format, ensure};
+use saa_crypto::hashes::{keccak256, sha256};
+
+
+/// Prefix byte of TRON mainnet addresses
+pub const ADDRESS_PREFIX : u8 = 0x41;
+
+
+/// Digest of a message signed with `signMessageV2` of TronWeb / TronLink
+pub fn preamble_msg_tron(msg: &[u8]) -> [u8; 32] {
+    const PREFIX: &str = "\x19TRON Signed Message:\n";
+    let mut bytes = vec![];
+    bytes.extend_from_slice(PREFIX.as_bytes());
+    let len_str = format!("{}", msg.len());
+    bytes.extend_from_slice(len_str.as_bytes());
+    bytes.extend_from_slice(msg);
+    keccak256(&bytes)
+}
+
+
+
+/// Decode a base58check `T...` address into the 20 bytes of the account
+pub fn address_to_bytes(address: &str) -> Result<[u8; 20], AuthError> {
+    let decoded = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| AuthError::generic(e.to_string()))?;
+    ensure!(decoded.len() == 25, AuthError::InvalidLength("TRON address".to_string(), 25, decoded.len() as u16));
+    ensure!(decoded[0] == ADDRESS_PREFIX, AuthError::generic("TRON address must start with 0x41 prefix"));
+
+    let checksum = sha256(&sha256(&decoded[..21]));
+    ensure!(checksum[..4] == decoded[21..], AuthError::generic("Invalid TRON address checksum"));
+
+    Ok(decoded[1..21].try_into().unwrap())
+}
+
+
+
+/// Encode 20 bytes of an account into a base58check `T...` address
+pub fn bytes_to_address(bytes: &[u8; 20]) -> String {
+    let mut data = vec![ADDRESS_PREFIX];
+    data.extend_from_slice(bytes);
+    let checksum = sha256(&sha256(&data));
+    data.extend_from_slice(&checksum[..4]);
+    bs58::encode(data).into_string()
+}
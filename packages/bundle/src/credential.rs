@@ -6,14 +6,28 @@ pub use super::caller::Caller;
 pub use saa_auth::eth::EthPersonalSign;
 #[cfg(feature = "cosmos")]
 pub use saa_auth::cosmos::CosmosArbitrary;
+#[cfg(feature = "cardano")]
+pub use saa_auth::cardano::CardanoSignData;
+#[cfg(feature = "tron")]
+pub use saa_auth::tron::TronSignMessage;
 #[cfg(feature = "passkeys")]
 pub use saa_passkeys::passkey::PasskeyCredential;
 #[cfg(feature = "secp256r1")]
 pub use saa_passkeys::secp256r1::Secp256r1;
 #[cfg(feature = "secp256k1")]
-pub use saa_curves::secp256k1::Secp256k1;
+pub use saa_curves::secp256k1::{Secp256k1, Secp256k1Recoverable};
 #[cfg(feature = "ed25519")]
 pub use saa_curves::ed25519::Ed25519;
+#[cfg(feature = "bls12_381")]
+pub use saa_curves::bls12_381::Bls12381;
+#[cfg(feature = "rsa")]
+pub use saa_curves::rsa::Rsa;
+#[cfg(feature = "ton")]
+pub use saa_auth::ton::TonProof;
+#[cfg(feature = "tezos")]
+pub use saa_auth::tezos::TezosSignPayload;
+#[cfg(feature = "stellar")]
+pub use saa_auth::stellar::StellarSignMessage;
 
 
 
@@ -27,6 +41,12 @@ pub enum Credential {
     #[cfg(feature = "cosmos")]
     CosmosArbitrary(CosmosArbitrary),
 
+    #[cfg(feature = "cardano")]
+    CardanoSignData(CardanoSignData),
+
+    #[cfg(feature = "tron")]
+    TronSignMessage(TronSignMessage),
+
     #[cfg(feature = "passkeys")]
     Passkey(PasskeyCredential),
 
@@ -36,9 +56,27 @@ pub enum Credential {
     #[cfg(feature = "secp256k1")]
     Secp256k1(Secp256k1),
 
+    #[cfg(feature = "secp256k1")]
+    Secp256k1Recoverable(Secp256k1Recoverable),
+
     #[cfg(feature = "ed25519")]
     Ed25519(Ed25519),
 
+    #[cfg(feature = "bls12_381")]
+    Bls12381(Bls12381),
+
+    #[cfg(feature = "rsa")]
+    Rsa(Rsa),
+
+    #[cfg(feature = "ton")]
+    TonProof(TonProof),
+
+    #[cfg(feature = "tezos")]
+    TezosSignPayload(TezosSignPayload),
+
+    #[cfg(feature = "stellar")]
+    StellarSignMessage(StellarSignMessage),
+
 }
 
 
@@ -61,12 +99,181 @@ pub type CredentialRecord = (CredentialId, CredentialInfo);
 
 
 
-#[allow(unused, dead_code)]
+/// Addresses that a credential maps to on different kinds of chains
+#[saa_type]
+#[derive(Default)]
+pub struct CredentialAddresses {
+    /// Bech32 address derived with the scheme of the credential's curve
+    pub cosmos  :   Option<String>,
+    /// EIP-55 checksummed 0x address of secp256k1 based credentials
+    pub evm     :   Option<String>,
+}
+
+
+
+/// Signing scheme of a raw curve credential kept in the `extension` of its stored info
+#[cfg(any(feature = "ed25519", feature = "secp256k1", feature = "secp256r1", feature = "bls12_381", feature = "rsa"))]
+#[saa_type]
+#[derive(Default)]
+pub struct CurveInfo {
+    #[cfg(feature = "ed25519")]
+    pub mode    :   Option<saa_curves::ed25519::Ed25519Mode>,
+    pub hash    :   Option<saa_crypto::hashes::HashAlgorithm>,
+    /// Treatment of high S signatures by ECDSA curves
+    #[cfg(any(feature = "secp256k1", feature = "secp256r1"))]
+    pub high_s  :   Option<saa_crypto::der::HighSPolicy>,
+    /// Domain separation tag of BLS12-381 signatures
+    #[cfg(feature = "bls12_381")]
+    pub dst     :   Option<String>,
+    /// Proofs of possession of aggregated BLS12-381 keys
+    #[cfg(feature = "bls12_381")]
+    pub proofs  :   Option<Binary>,
+    /// Signature scheme of RSA keys
+    #[cfg(feature = "rsa")]
+    pub scheme  :   Option<saa_crypto::RsaScheme>,
+}
+
+
+#[cfg(all(feature = "wasm", any(feature = "ed25519", feature = "secp256k1", feature = "secp256r1", feature = "bls12_381", feature = "rsa")))]
+impl CurveInfo {
+
+    pub(crate) fn from_extension(extension: Option<Binary>) -> Self {
+        extension
+            .and_then(|e| saa_common::from_json(&e).ok())
+            .unwrap_or_default()
+    }
+
+    /// Extension to store. Nothing is stored for the default scheme
+    pub(crate) fn to_extension(&self) -> Result<Option<Binary>, saa_common::AuthError> {
+        if *self == Self::default() {
+            return Ok(None);
+        }
+        Ok(Some(saa_common::to_json_binary(self)?))
+    }
+}
+
+
+
+/// Record to store for a passkey after verifying its registration ceremony
+#[cfg(all(feature = "passkeys", feature = "wasm"))]
+pub fn passkey_record(
+    id      : CredentialId,
+    info    : &saa_passkeys::passkey::PasskeyInfo,
+) -> Result<CredentialRecord, saa_common::AuthError> {
+    Ok((id, CredentialInfo {
+        name: CredentialName::Passkey,
+        hrp: None,
+        extension: Some(saa_common::to_json_binary(info)?),
+    }))
+}
+
+
+
+/// Check a verified credential against its stored info and return the info to store back.
+/// Passkeys must have a greater signature counter than the stored one that is replaced with it.
+/// Needed for both `build_credential` and `CredentialData` flows to detect cloned authenticators
+#[cfg(feature = "wasm")]
+#[allow(unused_mut, unused_variables)]
+pub fn update_credential_info(
+    mut info    : CredentialInfo,
+    credential  : &Credential,
+) -> Result<CredentialInfo, saa_common::AuthError> {
+    #[cfg(feature = "passkeys")]
+    if let Credential::Passkey(passkey) = credential {
+        let mut stored = info.extension.as_ref()
+            .and_then(|e| saa_common::from_json::<saa_passkeys::passkey::PasskeyInfo>(e).ok())
+            .ok_or_else(|| saa_common::AuthError::generic("Missing passkey info"))?;
+        stored.update_sign_count(passkey)?;
+        info.extension = Some(saa_common::to_json_binary(&stored)?);
+    }
+    Ok(info)
+}
+
+
+
+/// Pick the stored record to build a credential from. `AuthPayload::credential_id` is used when
+/// provided. Otherwise discoverable passkeys are located by the user handle of `PasskeyPayload`
+#[cfg(feature = "wasm")]
+pub fn find_credential_record(
+    records     : impl IntoIterator<Item = CredentialRecord>,
+    payload     : Option<&crate::msgs::AuthPayload>,
+) -> Result<CredentialRecord, saa_common::AuthError> {
+    use saa_common::{AuthError, StorageError, ToString};
+    let payload = payload.ok_or_else(|| AuthError::MissingData("Missing auth payload".to_string()))?;
+    if let Some(id) = payload.credential_id.as_ref() {
+        // records of secp256 keys might still be stored under uncompressed ids
+        let canonical = saa_crypto::keys::canonical_credential_id(id);
+        let sec1_keyed = |name: &CredentialName| match name {
+            #[cfg(feature = "secp256k1")]
+            CredentialName::Secp256k1 => true,
+            #[cfg(feature = "secp256r1")]
+            CredentialName::Secp256r1 => true,
+            #[cfg(feature = "passkeys")]
+            CredentialName::Passkey => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        };
+        return records.into_iter()
+            .find(|(record_id, info)| record_id == id || (
+                sec1_keyed(&info.name) && saa_crypto::keys::canonical_credential_id(record_id) == canonical
+            ))
+            .ok_or(AuthError::Storage(StorageError::NotFound));
+    }
+    #[cfg(feature = "passkeys")]
+    if let Some(handle) = payload.extension.as_ref()
+        .and_then(|e| saa_common::from_json::<saa_passkeys::passkey::PasskeyPayload>(e).ok())
+        .and_then(|p| p.user_handle)
+    {
+        let mut found = records.into_iter().filter(|(_, info)|
+            info.name == CredentialName::Passkey &&
+            info.extension.as_ref()
+                .and_then(|e| saa_common::from_json::<saa_passkeys::passkey::PasskeyInfo>(e).ok())
+                .is_some_and(|p| p.user_handle.as_ref() == Some(&handle))
+        );
+        let record = found.next().ok_or(AuthError::Storage(StorageError::NotFound))?;
+        saa_common::ensure!(
+            found.next().is_none(),
+            AuthError::generic("Multiple passkeys share the user handle. Specify the credential id")
+        );
+        return Ok(record);
+    }
+    Err(AuthError::MissingData("Either credential id or user handle must be provided".to_string()))
+}
+
+
+
 #[cfg(feature = "wasm")]
 pub fn build_credential(
     record      : CredentialRecord,
     msg         : crate::msgs::SignedDataMsg,
     extension   : Option<Binary>,
+) -> Result<Credential, saa_common::AuthError> {
+    build_credential_at(record, msg, extension, None)
+}
+
+
+
+/// Same as `build_credential` but also checks credentials bound to the current time
+/// such as `TonProof` against the block time of the environment
+#[cfg(feature = "wasm")]
+pub fn build_credential_env(
+    record      : CredentialRecord,
+    msg         : crate::msgs::SignedDataMsg,
+    extension   : Option<Binary>,
+    env         : &saa_common::wasm::Env,
+) -> Result<Credential, saa_common::AuthError> {
+    build_credential_at(record, msg, extension, Some(env.block.time.seconds()))
+}
+
+
+
+#[allow(unused, dead_code)]
+#[cfg(feature = "wasm")]
+fn build_credential_at(
+    record      : CredentialRecord,
+    msg         : crate::msgs::SignedDataMsg,
+    extension   : Option<Binary>,
+    now         : Option<u64>,
 ) -> Result<Credential, saa_common::AuthError> {
     let (id, info) = record;
     let message = msg.data;
@@ -93,6 +300,23 @@ pub fn build_credential(
             hrp: info.hrp,
         }),
 
+        #[cfg(feature = "cardano")]
+        CredentialName::CardanoSignData => Credential::CardanoSignData(CardanoSignData {
+            address: id,
+            message,
+            signature,
+            key: extension
+                .or(info.extension)
+                .ok_or_else(|| saa_common::AuthError::generic("Missing COSE key"))?,
+        }),
+
+        #[cfg(feature = "tron")]
+        CredentialName::TronSignMessage => Credential::TronSignMessage(TronSignMessage {
+            message,
+            signature,
+            signer: id,
+        }),
+
         #[cfg(feature = "passkeys")]
         CredentialName::Passkey => {
             use saa_passkeys::passkey::{
@@ -104,49 +328,150 @@ pub fn build_credential(
                 .flatten()
                 .ok_or_else(|| saa_common::AuthError::generic("Missing passkey info"))?;
             
-            let (origin, other_keys) = match extension
+            let (origin, other_keys, client_data_json, user_handle, authenticator_data) = match extension
                 .map(|e| saa_common::from_json::<PasskeyPayload>(e).ok())
                 .flatten()
             {
-                Some(payload) => (payload.origin, payload.other_keys),
-                None => (None, None),
+                Some(payload) => (
+                    payload.origin, payload.other_keys, payload.client_data_json, 
+                    payload.user_handle, payload.authenticator_data
+                ),
+                None => (None, None, None, None, None),
             };
+            stored_info.check_user_handle(user_handle.as_deref())?;
             
+            let policy = stored_info.effective_origin_policy();
             let client_data = ClientData::new(
                 base64_to_url(message.to_base64().as_str()),
-                origin.unwrap_or(stored_info.origin),
+                origin.unwrap_or(stored_info.origin.clone()),
                 stored_info.cross_origin,
                 other_keys
             );
 
-            Credential::Passkey(PasskeyCredential {
+            // the counter of the stored data is the stored one and can't be used for a new assertion
+            let authenticator_data = authenticator_data.ok_or_else(|| 
+                saa_common::AuthError::MissingData("Authenticator data of the assertion".to_string())
+            )?;
+            let credential = PasskeyCredential {
                 id,
                 signature,
                 client_data,
-                pubkey: Some(stored_info.pubkey),
-                authenticator_data: stored_info.authenticator_data,
-                user_handle: stored_info.user_handle,
-            })
+                client_data_json,
+                pubkey: Some(stored_info.pubkey.clone()),
+                authenticator_data,
+                user_handle: stored_info.user_handle.clone(),
+                authenticator_options: Some(stored_info.authenticator_options()),
+            };
+            // a payload may only switch to an origin allowed by the stored policy
+            credential.check_origin(&policy)?;
+            stored_info.check_rp_id_hash(&credential.parsed_authenticator_data()?.rp_id_hash)?;
+            Credential::Passkey(credential)
         },
 
         #[cfg(feature = "secp256r1")]
-        CredentialName::Secp256r1 => Credential::Secp256r1(Secp256r1 {
-            pubkey: Binary::from_base64(&id)?,
-            signature,
-            message,
-        }),
+        CredentialName::Secp256r1 => {
+            let curve = CurveInfo::from_extension(info.extension);
+            Credential::Secp256r1(Secp256r1 {
+                pubkey: Binary::from_base64(&id)?,
+                signature,
+                message,
+                hash: curve.hash,
+                high_s: curve.high_s,
+            })
+        },
+        #[cfg(feature = "secp256k1")]
+        CredentialName::Secp256k1 => {
+            let curve = CurveInfo::from_extension(info.extension);
+            Credential::Secp256k1(Secp256k1 {
+                pubkey: Binary::from_base64(&id)?,
+                signature,
+                message,
+                hrp: info.hrp,
+                hash: curve.hash,
+                high_s: curve.high_s,
+            })
+        },
         #[cfg(feature = "secp256k1")]
-        CredentialName::Secp256k1 => Credential::Secp256k1(Secp256k1 {
+        CredentialName::Secp256k1Recoverable => {
+            let curve = CurveInfo::from_extension(info.extension);
+            Credential::Secp256k1Recoverable(Secp256k1Recoverable {
+                signer: id,
+                signature,
+                message,
+                hash: curve.hash,
+                high_s: curve.high_s,
+            })
+        },
+        #[cfg(feature = "ed25519")]
+        CredentialName::Ed25519 => {
+            let curve = CurveInfo::from_extension(info.extension);
+            Credential::Ed25519(Ed25519 {
+                pubkey: Binary::from_base64(&id)?,
+                signature,
+                message,
+                mode: curve.mode,
+                hash: curve.hash,
+            })
+        },
+        #[cfg(feature = "bls12_381")]
+        CredentialName::Bls12381 => {
+            let curve = CurveInfo::from_extension(info.extension);
+            Credential::Bls12381(Bls12381 {
+                pubkey: Binary::from_base64(&id)?,
+                signature,
+                message,
+                dst: curve.dst,
+                proofs: curve.proofs,
+            })
+        },
+        #[cfg(feature = "rsa")]
+        CredentialName::Rsa => Credential::Rsa(Rsa {
             pubkey: Binary::from_base64(&id)?,
             signature,
             message,
-            hrp: info.hrp,
+            scheme: CurveInfo::from_extension(info.extension).scheme,
         }),
-        #[cfg(feature = "ed25519")]
-        CredentialName::Ed25519 => Credential::Ed25519(Ed25519 {
-            pubkey: Binary::from_base64(&id)?,
+
+        #[cfg(feature = "ton")]
+        CredentialName::TonProof => {
+            let payload = extension
+                .and_then(|e| saa_common::from_json::<saa_auth::ton::TonProofPayload>(e).ok())
+                .ok_or_else(|| saa_common::AuthError::generic("Missing ton_proof payload"))?;
+            let stored_info = info.extension
+                .and_then(|e| saa_common::from_json::<saa_auth::ton::TonProofInfo>(e).ok())
+                .ok_or_else(|| saa_common::AuthError::generic("Missing TON proof info"))?;
+            let now = now.ok_or_else(|| saa_common::AuthError::generic(
+                "Timestamp of ton_proof can only be checked with `build_credential_env`"
+            ))?;
+            Credential::TonProof(TonProof {
+                address: id,
+                pubkey: stored_info.pubkey.clone(),
+                domain: payload.domain,
+                timestamp: payload.timestamp,
+                message,
+                signature,
+                checks: Some(stored_info.checks(now)),
+            })
+        },
+
+        #[cfg(feature = "tezos")]
+        CredentialName::TezosSignPayload => Credential::TezosSignPayload(TezosSignPayload {
+            signer: id,
+            pubkey: String::from_utf8(
+                extension
+                    .or(info.extension)
+                    .ok_or_else(|| saa_common::AuthError::generic("Missing Tezos public key"))?
+                    .to_vec()
+            )?,
+            message,
             signature,
+        }),
+
+        #[cfg(feature = "stellar")]
+        CredentialName::StellarSignMessage => Credential::StellarSignMessage(StellarSignMessage {
             message,
+            signature,
+            signer: id,
         }),
     };
     Ok(credential)
@@ -1,9 +1,13 @@
 use core::ops::Deref;
 use strum::IntoDiscriminant;
-use saa_common::{ensure, AuthError, Binary, CredentialId, Verifiable};
+use saa_common::{ensure, AuthError, Binary, CredentialId, CryptoApi, Verifiable};
 use crate::{credential::CredentialName, Credential, CredentialData, CredentialInfo, caller::Caller};
 
 
+/// Signed data, signature and public key of a credential verifiable in an Ed25519 batch
+pub type Ed25519BatchItem = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+
 impl From<Caller> for Credential {
     fn from(c: Caller) -> Self {
         Credential::Native(c)
@@ -24,6 +28,21 @@ impl From<saa_auth::cosmos::CosmosArbitrary> for Credential {
     }
 }
 
+#[cfg(feature = "cardano")]
+impl From<saa_auth::cardano::CardanoSignData> for Credential {
+    fn from(c: saa_auth::cardano::CardanoSignData) -> Self {
+        Credential::CardanoSignData(c)
+    }
+}
+
+
+#[cfg(feature = "tron")]
+impl From<saa_auth::tron::TronSignMessage> for Credential {
+    fn from(c: saa_auth::tron::TronSignMessage) -> Self {
+        Credential::TronSignMessage(c)
+    }
+}
+
 
 #[cfg(feature = "ed25519")]
 impl From<saa_curves::ed25519::Ed25519> for Credential {
@@ -40,6 +59,27 @@ impl From<saa_curves::secp256k1::Secp256k1> for Credential {
     }
 }
 
+#[cfg(feature = "secp256k1")]
+impl From<saa_curves::secp256k1::Secp256k1Recoverable> for Credential {
+    fn from(c: saa_curves::secp256k1::Secp256k1Recoverable) -> Self {
+        Credential::Secp256k1Recoverable(c)
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+impl From<saa_curves::bls12_381::Bls12381> for Credential {
+    fn from(c: saa_curves::bls12_381::Bls12381) -> Self {
+        Credential::Bls12381(c)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl From<saa_curves::rsa::Rsa> for Credential {
+    fn from(c: saa_curves::rsa::Rsa) -> Self {
+        Credential::Rsa(c)
+    }
+}
+
 #[cfg(feature = "secp256r1")]
 impl From<saa_passkeys::secp256r1::Secp256r1> for Credential {
     fn from(c: saa_passkeys::secp256r1::Secp256r1) -> Self {
@@ -56,6 +96,30 @@ impl From<saa_passkeys::passkey::PasskeyCredential> for Credential {
 }
 
 
+#[cfg(feature = "ton")]
+impl From<saa_auth::ton::TonProof> for Credential {
+    fn from(c: saa_auth::ton::TonProof) -> Self {
+        Credential::TonProof(c)
+    }
+}
+
+
+#[cfg(feature = "tezos")]
+impl From<saa_auth::tezos::TezosSignPayload> for Credential {
+    fn from(c: saa_auth::tezos::TezosSignPayload) -> Self {
+        Credential::TezosSignPayload(c)
+    }
+}
+
+
+#[cfg(feature = "stellar")]
+impl From<saa_auth::stellar::StellarSignMessage> for Credential {
+    fn from(c: saa_auth::stellar::StellarSignMessage) -> Self {
+        Credential::StellarSignMessage(c)
+    }
+}
+
+
 
 impl Deref for Credential {
     type Target = dyn Verifiable;
@@ -67,14 +131,30 @@ impl Deref for Credential {
             Credential::EthPersonalSign(c) => c,
             #[cfg(feature = "cosmos")]
             Credential::CosmosArbitrary(c) => c,
+            #[cfg(feature = "cardano")]
+            Credential::CardanoSignData(c) => c,
+            #[cfg(feature = "tron")]
+            Credential::TronSignMessage(c) => c,
             #[cfg(feature = "passkeys")]
             Credential::Passkey(c) => c,
             #[cfg(feature = "secp256r1")]
             Credential::Secp256r1(c) => c,
             #[cfg(feature = "secp256k1")]
             Credential::Secp256k1(c) => c,
+            #[cfg(feature = "secp256k1")]
+            Credential::Secp256k1Recoverable(c) => c,
             #[cfg(feature = "ed25519")]
             Credential::Ed25519(c) => c,
+            #[cfg(feature = "bls12_381")]
+            Credential::Bls12381(c) => c,
+            #[cfg(feature = "rsa")]
+            Credential::Rsa(c) => c,
+            #[cfg(feature = "ton")]
+            Credential::TonProof(c) => c,
+            #[cfg(feature = "tezos")]
+            Credential::TezosSignPayload(c) => c,
+            #[cfg(feature = "stellar")]
+            Credential::StellarSignMessage(c) => c,
         }
     }
 }
@@ -94,18 +174,66 @@ impl Credential {
             Credential::EthPersonalSign(c) => c.message.to_vec(),
             #[cfg(feature = "cosmos")]
             Credential::CosmosArbitrary(c) => c.message.to_vec(),
+            #[cfg(feature = "cardano")]
+            Credential::CardanoSignData(c) => c.message.to_vec(),
+            #[cfg(feature = "tron")]
+            Credential::TronSignMessage(c) => c.message.to_vec(),
             #[cfg(feature = "ed25519")]
             Credential::Ed25519(c) => c.message.to_vec(),
+            #[cfg(feature = "bls12_381")]
+            Credential::Bls12381(c) => c.message.to_vec(),
+            #[cfg(feature = "rsa")]
+            Credential::Rsa(c) => c.message.to_vec(),
             #[cfg(feature = "secp256k1")]
             Credential::Secp256k1(c) => c.message.to_vec(),
+            #[cfg(feature = "secp256k1")]
+            Credential::Secp256k1Recoverable(c) => c.message.to_vec(),
             #[cfg(feature = "secp256r1")]
             Credential::Secp256r1(c) => c.message.to_vec(),
             #[cfg(feature = "passkeys")]
             Credential::Passkey(c) => c.base64_message_bytes().unwrap(),
+            #[cfg(feature = "ton")]
+            Credential::TonProof(c) => c.message.to_vec(),
+            #[cfg(feature = "tezos")]
+            Credential::TezosSignPayload(c) => c.message.to_vec(),
+            #[cfg(feature = "stellar")]
+            Credential::StellarSignMessage(c) => c.message.to_vec(),
         }
     }
 
     pub fn extension(&self) -> Result<Option<Binary>, AuthError> {
+        #[cfg(all(feature = "ton", feature = "wasm"))]
+        if let Credential::TonProof(c) = self {
+            return Ok(Some(saa_common::to_json_binary(&c.info())?));
+        }
+        #[cfg(feature = "tezos")]
+        if let Credential::TezosSignPayload(c) = self {
+            return Ok(Some(Binary::from(c.pubkey.as_bytes())));
+        }
+        #[cfg(all(feature = "ed25519", feature = "wasm"))]
+        if let Credential::Ed25519(c) = self {
+            return crate::credential::CurveInfo { mode: c.mode, hash: c.hash, ..Default::default() }.to_extension();
+        }
+        #[cfg(all(feature = "secp256k1", feature = "wasm"))]
+        if let Credential::Secp256k1(c) = self {
+            return crate::credential::CurveInfo { hash: c.hash, high_s: c.high_s, ..Default::default() }.to_extension();
+        }
+        #[cfg(all(feature = "secp256k1", feature = "wasm"))]
+        if let Credential::Secp256k1Recoverable(c) = self {
+            return crate::credential::CurveInfo { hash: c.hash, high_s: c.high_s, ..Default::default() }.to_extension();
+        }
+        #[cfg(all(feature = "secp256r1", feature = "wasm"))]
+        if let Credential::Secp256r1(c) = self {
+            return crate::credential::CurveInfo { hash: c.hash, high_s: c.high_s, ..Default::default() }.to_extension();
+        }
+        #[cfg(all(feature = "bls12_381", feature = "wasm"))]
+        if let Credential::Bls12381(c) = self {
+            return crate::credential::CurveInfo { dst: c.dst.clone(), proofs: c.proofs.clone(), ..Default::default() }.to_extension();
+        }
+        #[cfg(all(feature = "rsa", feature = "wasm"))]
+        if let Credential::Rsa(c) = self {
+            return crate::credential::CurveInfo { scheme: c.scheme, ..Default::default() }.to_extension();
+        }
         #[cfg(all(feature = "passkeys", feature = "wasm"))]
         if let Credential::Passkey(c) = self {
             use saa_passkeys::passkey::*;
@@ -115,6 +243,8 @@ impl Credential {
                 pubkey: c.pubkey.clone().unwrap_or_default(),
                 user_handle: c.user_handle.clone(),
                 authenticator_data: c.authenticator_data.clone(),
+                origin_policy: None,
+                sign_count: c.parsed_authenticator_data().ok().map(|d| d.sign_count),
             })?));
         }
         Ok(None)
@@ -128,6 +258,24 @@ impl Credential {
         }
     }
 
+    /// Signed data, signature and public key of credentials that are verified with nothing but
+    /// a single Ed25519 signature check and therefore can be a part of a batch verification
+    #[allow(unused_variables)]
+    pub fn ed25519_batch_item(&self, api: &dyn CryptoApi) -> Option<Ed25519BatchItem> {
+        match self {
+            #[cfg(feature = "ed25519")]
+            Credential::Ed25519(c) => c.batch_data(api)?.ok()
+                .map(|data| (data, c.signature.to_vec(), c.pubkey.to_vec())),
+            #[cfg(feature = "stellar")]
+            Credential::StellarSignMessage(c) => {
+                use saa_auth::stellar::utils::{decode_account_id, preamble_msg_stellar};
+                let pubkey = decode_account_id(&c.signer).ok()?;
+                Some((preamble_msg_stellar(&c.message), c.signature.to_vec(), pubkey.to_vec()))
+            },
+            _ => None,
+        }
+    }
+
     
 }
 
@@ -189,15 +337,24 @@ impl Verifiable for CredentialData {
     }
 
 
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        self.verify_batched(api, |c| c.verify_with(api))
+    }
+
+
     #[cfg(feature = "native")]
     fn verify(&self) -> Result<(), AuthError> {
-        self.credentials.iter().try_for_each(|c| c.verify())
+        self.verify_with(&saa_crypto::NativeCrypto)
     }
 
 
+    /// Credentials are verified with their own `verify_cosmwasm` to let them use the rest of the API
     #[cfg(feature = "wasm")]
     fn verify_cosmwasm(&self,  api : &dyn saa_common::wasm::Api) -> Result<(), AuthError>  {
-        self.credentials.iter().try_for_each(|c| c.verify_cosmwasm(api))
+        self.verify_batched(
+            &saa_crypto::CosmwasmCrypto(api),
+            |c| c.verify_cosmwasm(api)
+        )
     }
 
 }
@@ -208,6 +365,39 @@ impl Verifiable for CredentialData {
 
 impl CredentialData {
 
+    /// Verify all the Ed25519 based credentials with a single batch call and the rest one by one.
+    /// If the batch fails every credential is verified separately to find out which one is invalid
+    fn verify_batched<V>(&self, api: &dyn CryptoApi, verify: V) -> Result<(), AuthError> 
+        where V: Fn(&Credential) -> Result<(), AuthError>
+    {
+        let items : Vec<Option<Ed25519BatchItem>> = self.credentials
+            .iter()
+            .map(|c| c.ed25519_batch_item(api))
+            .collect();
+        
+        let batched = items.iter().flatten().count() > 1 && {
+            let messages : Vec<&[u8]> = items.iter().flatten().map(|i| i.0.as_slice()).collect();
+            let signatures : Vec<&[u8]> = items.iter().flatten().map(|i| i.1.as_slice()).collect();
+            let pubkeys : Vec<&[u8]> = items.iter().flatten().map(|i| i.2.as_slice()).collect();
+            api.ed25519_batch_verify(&messages, &signatures, &pubkeys).unwrap_or(false)
+        };
+
+        self.credentials
+            .iter()
+            .zip(items.iter())
+            .filter(|(_, item)| !(batched && item.is_some()))
+            .try_for_each(|(c, _)| verify(c))
+    }
+
+    /// Verify the credentials with the host functions of an ink! contract
+    #[cfg(feature = "substrate")]
+    pub fn verify_ink<E: saa_common::substrate::InkEnvironment>(
+        &self, 
+        api: saa_common::substrate::InkApi<'_, E>
+    ) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::InkCrypto(api))
+    }
+
     fn cred_index(&self, name: CredentialName, id: Option<CredentialId>) -> Option<usize> {
         self.credentials.iter()
             .position(|c| c.name() == name && 
@@ -40,8 +40,20 @@ pub mod types {
     pub use saa_common::types::*;
     #[cfg(feature = "passkeys")]
     pub use saa_passkeys::passkey::{
-        ClientData, ClientDataOtherKeys, PasskeyInfo, PasskeyPayload
+        ClientData, ClientDataOtherKeys, ClientDataValue, PasskeyInfo, PasskeyPayload,
+        PasskeyRegistration, RegistrationOptions, AuthenticatorOptions, PasskeyPublicKey, OriginPolicy,
+        ExtensionOutputs, CredProtect, HmacSecretOutput, DevicePublicKey
     };
+    #[cfg(feature = "ton")]
+    pub use saa_auth::ton::{TonProofPayload, TonProofChecks, TonProofInfo};
+    #[cfg(feature = "ed25519")]
+    pub use saa_curves::ed25519::Ed25519Mode;
+    #[cfg(any(feature = "ed25519", feature = "secp256k1", feature = "secp256r1"))]
+    pub use saa_crypto::hashes::HashAlgorithm;
+    #[cfg(any(feature = "secp256k1", feature = "secp256r1"))]
+    pub use saa_crypto::der::HighSPolicy;
+    #[cfg(feature = "rsa")]
+    pub use saa_crypto::RsaScheme;
     #[cfg(feature = "session")]
     pub use super::messages::actions::{ActionDerivation, AllQueryDerivation};
 }
@@ -56,8 +68,18 @@ pub mod utils {
     pub use saa_auth::cosmos::utils as cosmos;
     #[cfg(feature = "ethereum")]
     pub use saa_auth::eth::utils as eth;
+    #[cfg(feature = "cardano")]
+    pub use saa_auth::cardano::utils as cardano;
+    #[cfg(feature = "tron")]
+    pub use saa_auth::tron::utils as tron;
     #[cfg(feature = "passkeys")]
     pub use saa_passkeys::passkey::utils as passkey;
+    #[cfg(feature = "ton")]
+    pub use saa_auth::ton::utils as ton;
+    #[cfg(feature = "tezos")]
+    pub use saa_auth::tezos::utils as tezos;
+    #[cfg(feature = "stellar")]
+    pub use saa_auth::stellar::utils as stellar;
 }
 
 #[cfg(feature = "traits")]
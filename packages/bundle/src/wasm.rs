@@ -1,7 +1,8 @@
-use crate::credential::{Credential, CredentialInfo, CredentialName};
+use crate::credential::{Credential, CredentialAddresses, CredentialInfo, CredentialName};
 use saa_common::{wasm::{Addr, Api}, AuthError, CredentialId};
 #[cfg(feature= "wasm")]
 use saa_crypto::{pubkey_to_address, pubkey_to_canonical};
+use saa_common::Vec;
 
 
 
@@ -17,6 +18,83 @@ impl Credential {
         ok
     }
 
+    /// Account bytes derived from the public key of the credential using the scheme of its curve:
+    /// - secp256k1: `ripemd160(sha256(key))`
+    /// - secp256r1: `sha256(sha256("cosmos.crypto.secp256r1.PubKey") || key)` as in the Cosmos SDK
+    /// - ed25519: first 20 bytes of `sha256(key)`
+    ///
+    /// `None` for credentials that don't carry a public key with a known scheme
+    pub fn cosmos_account(&self) -> Result<Option<Vec<u8>>, AuthError> {
+        #[allow(unused_imports)]
+        use saa_crypto::{secp256k1_account, secp256r1_account, ed25519_account};
+        Ok(match self {
+            #[cfg(feature = "cosmos")]
+            Credential::CosmosArbitrary(c) => Some(secp256k1_account(&c.pubkey)?),
+            #[cfg(feature = "secp256k1")]
+            Credential::Secp256k1(c) => Some(secp256k1_account(&c.pubkey)?),
+            #[cfg(feature = "secp256k1")]
+            Credential::Secp256k1Recoverable(c) => c.signer_pubkey()
+                .map(|pubkey| secp256k1_account(&pubkey))
+                .transpose()?,
+            #[cfg(feature = "secp256r1")]
+            Credential::Secp256r1(c) => Some(secp256r1_account(&c.pubkey)?),
+            #[cfg(feature = "ed25519")]
+            Credential::Ed25519(c) => Some(ed25519_account(&c.pubkey)?),
+            #[cfg(feature = "passkeys")]
+            Credential::Passkey(c) => {
+                use saa_passkeys::passkey::PasskeyPublicKey;
+                let pubkey = c.pubkey.as_ref()
+                    .ok_or_else(|| AuthError::generic("Missing public key"))?;
+                match PasskeyPublicKey::decode(pubkey)? {
+                    PasskeyPublicKey::Es256(key) => Some(secp256r1_account(&key)?),
+                    PasskeyPublicKey::EdDsa(key) => Some(ed25519_account(&key)?),
+                    _ => None,
+                }
+            },
+            #[allow(unreachable_patterns)]
+            _ => None,
+        })
+    }
+
+    /// Derive the addresses of the credential using the scheme of its curve (see `cosmos_account`)
+    /// and keccak256 based 0x address of secp256k1 keys.
+    ///
+    /// Credentials that already are addresses keep them as they are
+    pub fn addresses(&self, hrp: &str) -> Result<CredentialAddresses, AuthError> {
+        let cosmos = match self.cosmos_account()? {
+            Some(account) => Some(saa_crypto::bech32_address(&account, hrp)?),
+            None => match self {
+                Credential::Native(caller) => Some(caller.0.clone()),
+                #[cfg(feature = "secp256k1")]
+                Credential::Secp256k1Recoverable(c) => Some(c.signer.clone()),
+                #[allow(unreachable_patterns)]
+                _ => None,
+            }
+        };
+        #[allow(unused_mut)]
+        let mut evm = None;
+        #[cfg(feature = "eth_personal")]
+        if let Credential::EthPersonalSign(c) = self {
+            evm = Some(c.signer.clone());
+        }
+        #[cfg(feature = "secp256k1")]
+        {
+            let pubkey = match self {
+                #[cfg(feature = "cosmos")]
+                Credential::CosmosArbitrary(c) => Some(c.pubkey.to_vec()),
+                Credential::Secp256k1(c) => Some(c.pubkey.to_vec()),
+                Credential::Secp256k1Recoverable(c) => c.signer_pubkey().map(|key| key.to_vec()),
+                _ => None,
+            };
+            if let Some(pubkey) = pubkey {
+                evm = Some(saa_crypto::keys::evm_address(&pubkey)?);
+            }
+        }
+        Ok(CredentialAddresses { cosmos, evm })
+    }
+
+    /// Cosmos address of the credential. Public keys are derived with the scheme of their curve
+    /// and other credentials fall back to `ripemd160(sha256(id))`
     pub fn cosmos_address(&self, api: &dyn Api) -> Result<Addr, AuthError> {
         let id = self.id();
         let name = self.name();
@@ -24,30 +102,67 @@ impl Credential {
             let addr = api.addr_validate(&id)?;
             return Ok(addr)
         }
+        let account = match self.cosmos_account()? {
+            Some(account) => account,
+            None => pubkey_to_canonical(id.as_bytes()).to_vec(),
+        };
         Ok(match self.hrp() {
-            Some(hrp) => Addr::unchecked(
-                pubkey_to_address(id.as_bytes(), &hrp)?
-            ),
-            None => {
-                let canon = pubkey_to_canonical(id.as_bytes());
-                let addr = api.addr_humanize(&canon)?;
-                addr
-            }
+            Some(hrp) => Addr::unchecked(saa_crypto::bech32_address(&account, &hrp)?),
+            None => api.addr_humanize(&account.into())?,
         })
     }
 
+    /// Address derived from `ripemd160(sha256(id))` for every credential as `cosmos_address` did
+    /// before the curve schemes. Meant for contracts that already stored accounts under it
+    pub fn legacy_cosmos_address(&self, api: &dyn Api) -> Result<Addr, AuthError> {
+        self.info().legacy_cosmos_address(api, self.id())
+    }
+
 }
 
 
 impl CredentialInfo {
     
 
+    /// Account bytes of a stored credential whose id is its public key (see `Credential::cosmos_account`)
+    pub fn cosmos_account(&self, id: &str) -> Result<Option<Vec<u8>>, AuthError> {
+        #[allow(unused_imports)]
+        use saa_crypto::{secp256k1_account, secp256r1_account, ed25519_account};
+        #[allow(unused_variables)]
+        let pubkey = || saa_common::Binary::from_base64(id);
+        Ok(match self.name {
+            #[cfg(feature = "cosmos")]
+            CredentialName::CosmosArbitrary => Some(secp256k1_account(&pubkey()?)?),
+            #[cfg(feature = "secp256k1")]
+            CredentialName::Secp256k1 => Some(secp256k1_account(&pubkey()?)?),
+            #[cfg(feature = "secp256r1")]
+            CredentialName::Secp256r1 => Some(secp256r1_account(&pubkey()?)?),
+            #[cfg(feature = "ed25519")]
+            CredentialName::Ed25519 => Some(ed25519_account(&pubkey()?)?),
+            _ => None,
+        })
+    }
+
     pub fn cosmos_address(&self, api: &dyn Api, id: CredentialId) -> Result<Addr, crate::AuthError> {
         let name = self.name.clone();
         if name == CredentialName::Native {
             let addr = api.addr_validate(&id)?;
             return Ok(addr)
         }
+        if let Some(account) = self.cosmos_account(&id)? {
+            return Ok(match &self.hrp {
+                Some(hrp) => api.addr_validate(&saa_crypto::bech32_address(&account, hrp)?)?,
+                None => api.addr_humanize(&account.into())?,
+            })
+        }
+        self.legacy_cosmos_address(api, id)
+    }
+
+    /// Same as `Credential::legacy_cosmos_address` for a stored credential
+    pub fn legacy_cosmos_address(&self, api: &dyn Api, id: CredentialId) -> Result<Addr, crate::AuthError> {
+        if self.name == CredentialName::Native {
+            return Ok(api.addr_validate(&id)?)
+        }
         Ok(match &self.hrp {
             Some(hrp) => api.addr_validate(&pubkey_to_address(id.as_bytes(), &hrp)?)?,
             None => api.addr_humanize(&pubkey_to_canonical(id.as_bytes()))?,
@@ -9,6 +9,6 @@ pub use types::errors::*;
 pub use types::binary::*;
 pub use types::uints::Uint64;
 pub use types::exp::Expiration;
-pub use traits::Verifiable;
+pub use traits::{Verifiable, CryptoApi};
 
 pub type CredentialId = String;
\ No newline at end of file
@@ -1,5 +1,93 @@
 use core::ops::Deref;
-use crate::{AuthError, CredentialId};
+use crate::{AuthError, CredentialId, Vec, ensure, format};
+
+
+fn unsupported(primitive: &str) -> AuthError {
+    AuthError::generic(format!("{} is not supported by the crypto backend", primitive))
+}
+
+
+/// Cryptographic primitives of an environment that credentials are verified with.
+/// Backends only implement what their environment supports and the rest fails when called
+pub trait CryptoApi {
+
+    fn sha256(&self, _data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Err(unsupported("sha256"))
+    }
+
+    fn keccak256(&self, _data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Err(unsupported("keccak256"))
+    }
+
+    fn secp256k1_verify(
+        &self, 
+        _message_hash: &[u8], 
+        _signature: &[u8], 
+        _public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        Err(unsupported("secp256k1_verify"))
+    }
+
+    /// Public key in uncompressed SEC1 form recovered from a compact signature
+    fn secp256k1_recover_pubkey(
+        &self, 
+        _message_hash: &[u8], 
+        _signature: &[u8], 
+        _recovery_param: u8
+    ) -> Result<Vec<u8>, AuthError> {
+        Err(unsupported("secp256k1_recover_pubkey"))
+    }
+
+    fn secp256r1_verify(
+        &self, 
+        _message_hash: &[u8], 
+        _signature: &[u8], 
+        _public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        Err(unsupported("secp256r1_verify"))
+    }
+
+    fn ed25519_verify(
+        &self, 
+        _message: &[u8], 
+        _signature: &[u8], 
+        _public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        Err(unsupported("ed25519_verify"))
+    }
+
+    /// Verify a BLS12-381 signature or an aggregate of them with public keys in G1 and signatures in G2.
+    /// The keys and the signatures are concatenated and aggregated when there are more than one
+    fn bls12_381_verify(
+        &self, 
+        _message: &[u8], 
+        _signatures: &[u8], 
+        _public_keys: &[u8],
+        _dst: &[u8]
+    ) -> Result<bool, AuthError> {
+        Err(unsupported("bls12_381_verify"))
+    }
+
+    /// Verify the signatures of the same index at once. Checks them one by one unless overridden
+    fn ed25519_batch_verify(
+        &self, 
+        messages: &[&[u8]], 
+        signatures: &[&[u8]], 
+        public_keys: &[&[u8]]
+    ) -> Result<bool, AuthError> {
+        ensure!(
+            messages.len() == signatures.len() && signatures.len() == public_keys.len(),
+            AuthError::generic("Batch must have the same number of messages, signatures and public keys")
+        );
+        for ((message, signature), public_key) in messages.iter().zip(signatures).zip(public_keys) {
+            if !self.ed25519_verify(message, signature, public_key)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
 
 
 pub trait Verifiable  {
@@ -12,6 +100,11 @@ pub trait Verifiable  {
 
     fn validate(&self) -> Result<(), AuthError>;
 
+    /// Verify the credential using the primitives of the given crypto backend
+    fn verify_with(&self, _: &dyn CryptoApi) -> Result<(), AuthError> {
+        Err(AuthError::generic("Not implemented"))
+    }
+
     #[cfg(feature = "native")]
     fn verify(&self) -> Result<(), AuthError>;
 
@@ -43,6 +136,10 @@ impl<T: Deref<Target = dyn Verifiable>> Verifiable for T {
         self.deref().validate()
     }
 
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        self.deref().verify_with(api)
+    }
+
     #[cfg(feature = "native")]
     fn verify(&self) -> Result<(), AuthError> {
         self.deref().verify()
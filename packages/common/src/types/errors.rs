@@ -110,6 +110,9 @@ mod std_mod {
         #[error("Passkey challenge must be base64url to base64 encoded string")]
         PasskeyChallenge,
 
+        #[error("Origin {0} is not allowed")]
+        OriginNotAllowed(String),
+
         #[error("Unauthorized: {0}")]
         Unauthorized(String),
 
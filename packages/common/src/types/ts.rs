@@ -3,6 +3,13 @@ use saa_schema::saa_str_struct;
 use super::uints::Uint64;
 
 #[saa_str_struct]
+#[cfg_attr(feature = "substrate", derive(
+    saa_schema::scale::Encode, 
+    saa_schema::scale::Decode
+))]
+#[cfg_attr(all(feature = "std", feature="substrate"), derive(
+    saa_schema::scale_info::TypeInfo)
+)]
 pub struct Timestamp(Uint64);
 
 impl Timestamp {
@@ -4,6 +4,13 @@ use serde::{de, ser, Deserialize, Deserializer, Serialize};
 
 
 #[saa_str_struct]
+#[cfg_attr(feature = "substrate", derive(
+    saa_schema::scale::Encode, 
+    saa_schema::scale::Decode
+))]
+#[cfg_attr(all(feature = "std", feature="substrate"), derive(
+    saa_schema::scale_info::TypeInfo)
+)]
 pub struct Uint64(#[cfg_attr(feature = "wasm", schemars(with = "String"))]pub(crate) u64);
 
 
@@ -0,0 +1,339 @@
+use saa_common::{AuthError, CryptoApi, Vec};
+#[cfg(feature = "substrate")]
+use saa_common::substrate::{
+    InkApi, InkEnvironment, default::DefaultEnvironment,
+    ink_env::hash::{Keccak256, Sha2x256},
+};
+
+
+#[allow(dead_code)]
+fn unsupported(primitive: &str) -> AuthError {
+    AuthError::generic(saa_common::format!("{} requires a crypto feature that isn't enabled", primitive))
+}
+
+
+fn sha256_array(data: &[u8]) -> [u8; 32] {
+    crate::hashes::sha256(data).try_into().unwrap()
+}
+
+
+/// Pure Rust implementation of the primitives for environments without host functions.
+/// Curves are only available when the features of their crates are enabled:
+/// `secp256k1`, `secp256r1` and `ed25519ph` for Ed25519
+pub struct RustCrypto;
+
+
+impl CryptoApi for RustCrypto {
+
+    fn sha256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Ok(sha256_array(data))
+    }
+
+    fn keccak256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        #[cfg(any(feature = "ethereum", feature = "digests"))]
+        return Ok(crate::hashes::keccak256(data));
+        #[cfg(not(any(feature = "ethereum", feature = "digests")))]
+        {
+            let _ = data;
+            Err(unsupported("keccak256"))
+        }
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        #[cfg(feature = "secp256k1")]
+        {
+            use k256::ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+            let mut signature = Signature::from_slice(signature)
+                .map_err(|_| AuthError::generic("Invalid secp256k1 signature"))?;
+            // high S is accepted like by the other backends
+            if let Some(normalized) = signature.normalize_s() {
+                signature = normalized;
+            }
+            let key = VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| AuthError::generic("Invalid secp256k1 public key"))?;
+            Ok(key.verify_prehash(message_hash, &signature).is_ok())
+        }
+        #[cfg(not(feature = "secp256k1"))]
+        {
+            let _ = (message_hash, signature, public_key);
+            Err(unsupported("secp256k1_verify"))
+        }
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8
+    ) -> Result<Vec<u8>, AuthError> {
+        #[cfg(feature = "secp256k1")]
+        {
+            use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+            let mut id = RecoveryId::from_byte(recovery_param).filter(|id| !id.is_x_reduced())
+                .ok_or(AuthError::RecoveryParam)?;
+            let mut signature = Signature::from_slice(signature)
+                .map_err(|_| AuthError::generic("Invalid secp256k1 signature"))?;
+            if let Some(normalized) = signature.normalize_s() {
+                signature = normalized;
+                id = RecoveryId::new(!id.is_y_odd(), id.is_x_reduced());
+            }
+            let key = VerifyingKey::recover_from_prehash(message_hash, &signature, id)
+                .map_err(|_| AuthError::generic("Public key recovery failed"))?;
+            Ok(key.to_encoded_point(false).as_bytes().to_vec())
+        }
+        #[cfg(not(feature = "secp256k1"))]
+        {
+            let _ = (message_hash, signature, recovery_param);
+            Err(unsupported("secp256k1_recover_pubkey"))
+        }
+    }
+
+    fn secp256r1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        #[cfg(feature = "secp256r1")]
+        return crate::secp256r1::secp256r1_verify(message_hash, signature, public_key);
+        #[cfg(not(feature = "secp256r1"))]
+        {
+            let _ = (message_hash, signature, public_key);
+            Err(unsupported("secp256r1_verify"))
+        }
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8]
+    ) -> Result<bool, AuthError> {
+        #[cfg(feature = "ed25519ph")]
+        {
+            use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+            let public_key : &[u8; 32] = public_key.try_into()
+                .map_err(|_| AuthError::InvalidLength("Ed25519 public key".into(), 32, public_key.len() as u16))?;
+            let key = VerifyingKey::from_bytes(public_key)
+                .map_err(|_| AuthError::generic("Invalid Ed25519 public key"))?;
+            let signature = Signature::from_slice(signature)
+                .map_err(|_| AuthError::InvalidLength("Ed25519 signature".into(), 64, signature.len() as u16))?;
+            Ok(key.verify(message, &signature).is_ok())
+        }
+        #[cfg(not(feature = "ed25519ph"))]
+        {
+            let _ = (message, signature, public_key);
+            Err(unsupported("ed25519_verify"))
+        }
+    }
+
+    fn bls12_381_verify(
+        &self,
+        message: &[u8],
+        signatures: &[u8],
+        public_keys: &[u8],
+        dst: &[u8]
+    ) -> Result<bool, AuthError> {
+        #[cfg(any(feature = "native", feature = "bls12_381"))]
+        return crate::bls12_381::bls12_381_verify(message, signatures, public_keys, dst);
+        #[cfg(not(any(feature = "native", feature = "bls12_381")))]
+        {
+            let _ = (message, signatures, public_keys, dst);
+            Err(unsupported("bls12_381_verify"))
+        }
+    }
+}
+
+
+
+/// Primitives of `cosmwasm-crypto` used when verifying outside of a virtual machine
+#[cfg(feature = "native")]
+pub struct NativeCrypto;
+
+
+#[cfg(feature = "native")]
+impl CryptoApi for NativeCrypto {
+
+    fn sha256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Ok(sha256_array(data))
+    }
+
+    fn keccak256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        RustCrypto.keccak256(data)
+    }
+
+    fn secp256k1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        Ok(cosmwasm_crypto::secp256k1_verify(message_hash, signature, public_key)?)
+    }
+
+    fn secp256k1_recover_pubkey(&self, message_hash: &[u8], signature: &[u8], recovery_param: u8) -> Result<Vec<u8>, AuthError> {
+        Ok(cosmwasm_crypto::secp256k1_recover_pubkey(message_hash, signature, recovery_param)?)
+    }
+
+    fn secp256r1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        Ok(cosmwasm_crypto::secp256r1_verify(message_hash, signature, public_key)?)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        Ok(cosmwasm_crypto::ed25519_verify(message, signature, public_key)?)
+    }
+
+    fn ed25519_batch_verify(&self, messages: &[&[u8]], signatures: &[&[u8]], public_keys: &[&[u8]]) -> Result<bool, AuthError> {
+        crate::native::ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn bls12_381_verify(&self, message: &[u8], signatures: &[u8], public_keys: &[u8], dst: &[u8]) -> Result<bool, AuthError> {
+        crate::bls12_381::bls12_381_verify(message, signatures, public_keys, dst)
+    }
+}
+
+
+
+/// Host functions of a CosmWasm chain. Hashing runs inside of the contract and so does
+/// secp256r1 verification with `secp256r1` feature or on chains without the host function
+#[cfg(feature = "cosmwasm")]
+pub struct CosmwasmCrypto<'a>(pub &'a dyn saa_common::wasm::Api);
+
+
+#[cfg(feature = "cosmwasm")]
+impl CryptoApi for CosmwasmCrypto<'_> {
+
+    fn sha256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Ok(sha256_array(data))
+    }
+
+    fn keccak256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        RustCrypto.keccak256(data)
+    }
+
+    fn secp256k1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        Ok(self.0.secp256k1_verify(message_hash, signature, public_key)?)
+    }
+
+    fn secp256k1_recover_pubkey(&self, message_hash: &[u8], signature: &[u8], recovery_param: u8) -> Result<Vec<u8>, AuthError> {
+        Ok(self.0.secp256k1_recover_pubkey(message_hash, signature, recovery_param)?)
+    }
+
+    /// Chains without the host function of secp256r1 (`no_api_r1`) verify the signatures inside of the contract
+    fn secp256r1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        #[cfg(feature = "no_api_r1")]
+        return RustCrypto.secp256r1_verify(message_hash, signature, public_key);
+        #[cfg(not(feature = "no_api_r1"))]
+        Ok(self.0.secp256r1_verify(message_hash, signature, public_key)?)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        Ok(self.0.ed25519_verify(message, signature, public_key)?)
+    }
+
+    fn ed25519_batch_verify(&self, messages: &[&[u8]], signatures: &[&[u8]], public_keys: &[&[u8]]) -> Result<bool, AuthError> {
+        Ok(self.0.ed25519_batch_verify(messages, signatures, public_keys)?)
+    }
+
+    /// Chains without the host functions of BLS12-381 (`no_api_bls`) aggregate and verify
+    /// the signatures inside of the contract with `bls12_381` feature and can't verify them otherwise
+    fn bls12_381_verify(&self, message: &[u8], signatures: &[u8], public_keys: &[u8], dst: &[u8]) -> Result<bool, AuthError> {
+        #[cfg(all(feature = "no_api_bls", feature = "bls12_381"))]
+        return RustCrypto.bls12_381_verify(message, signatures, public_keys, dst);
+        #[cfg(all(feature = "no_api_bls", not(feature = "bls12_381")))]
+        {
+            let _ = (message, signatures, public_keys, dst);
+            Err(unsupported("bls12_381_verify"))
+        }
+        #[cfg(not(feature = "no_api_bls"))]
+        {
+            use saa_common::wasm::HashFunction;
+            use crate::bls12_381::{BLS12_381_G1_GENERATOR, BLS12_381_PUBKEY_LEN, BLS12_381_SIGNATURE_LEN};
+            let pubkey = match public_keys.len() {
+                BLS12_381_PUBKEY_LEN => public_keys.to_vec(),
+                _ => self.0.bls12_381_aggregate_g1(public_keys)?.to_vec(),
+            };
+            let signature = match signatures.len() {
+                BLS12_381_SIGNATURE_LEN => signatures.to_vec(),
+                _ => self.0.bls12_381_aggregate_g2(signatures)?.to_vec(),
+            };
+            let hashed = self.0.bls12_381_hash_to_g2(HashFunction::Sha256, message, dst)?;
+            Ok(self.0.bls12_381_pairing_equality(&BLS12_381_G1_GENERATOR, &signature, &pubkey, &hashed)?)
+        }
+    }
+}
+
+
+
+/// Host functions of `pallet-contracts` available to ink! contracts. The pallet can only recover
+/// secp256k1 keys so the signatures are verified by comparing the recovered key with the given one.
+/// Secp256r1 and Ed25519 aren't exposed by the pallet and are verified inside of the contract
+/// like by `RustCrypto`. Ed25519 is always available since `substrate` enables `ed25519ph`
+/// and secp256r1 requires `secp256r1` feature
+#[cfg(feature = "substrate")]
+pub struct InkCrypto<'a, E: InkEnvironment = DefaultEnvironment>(pub InkApi<'a, E>);
+
+
+#[cfg(feature = "substrate")]
+impl<E: InkEnvironment> InkCrypto<'_, E> {
+
+    fn recover_compressed(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8
+    ) -> Result<[u8; 33], AuthError> {
+        use k256::ecdsa::{RecoveryId, Signature};
+        let message_hash : &[u8; 32] = message_hash.try_into()
+            .map_err(|_| AuthError::InvalidLength("Message hash".into(), 32, message_hash.len() as u16))?;
+        let mut id = RecoveryId::from_byte(recovery_param).ok_or(AuthError::RecoveryParam)?;
+        // checked here since the off-chain environment panics on malformed signatures
+        let mut signature = Signature::from_slice(signature)
+            .map_err(|_| AuthError::generic("Invalid secp256k1 signature"))?;
+        if let Some(normalized) = signature.normalize_s() {
+            signature = normalized;
+            id = RecoveryId::new(!id.is_y_odd(), id.is_x_reduced());
+        }
+        let mut recoverable = [0u8; 65];
+        recoverable[..64].copy_from_slice(&signature.to_bytes());
+        recoverable[64] = id.to_byte();
+        self.0.clone().ecdsa_recover(&recoverable, message_hash)
+            .map_err(|_| AuthError::generic("Public key recovery failed"))
+    }
+}
+
+
+#[cfg(feature = "substrate")]
+impl<E: InkEnvironment> CryptoApi for InkCrypto<'_, E> {
+
+    fn sha256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Ok(self.0.clone().hash_bytes::<Sha2x256>(data))
+    }
+
+    fn keccak256(&self, data: &[u8]) -> Result<[u8; 32], AuthError> {
+        Ok(self.0.clone().hash_bytes::<Keccak256>(data))
+    }
+
+    fn secp256k1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        let public_key = crate::keys::compress_sec1(public_key)?;
+        for recovery_param in 0..2 {
+            if self.recover_compressed(message_hash, signature, recovery_param)? == public_key {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn secp256k1_recover_pubkey(&self, message_hash: &[u8], signature: &[u8], recovery_param: u8) -> Result<Vec<u8>, AuthError> {
+        let compressed = self.recover_compressed(message_hash, signature, recovery_param)?;
+        Ok(crate::keys::secp256k1_decompress(&compressed)?.to_vec())
+    }
+
+    fn secp256r1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        RustCrypto.secp256r1_verify(message_hash, signature, public_key)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, AuthError> {
+        RustCrypto.ed25519_verify(message, signature, public_key)
+    }
+}
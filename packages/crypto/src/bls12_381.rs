@@ -0,0 +1,44 @@
+/// Length of a compressed G1 point that public keys are encoded as
+pub const BLS12_381_PUBKEY_LEN : usize = 48;
+/// Length of a compressed G2 point that signatures are encoded as
+pub const BLS12_381_SIGNATURE_LEN : usize = 96;
+
+/// Domain separation tag of the proof of possession scheme with public keys in G1
+pub const BLS12_381_DST_POP : &str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Domain separation tag of the proofs of possession signing the compressed public keys
+pub const BLS12_381_DST_POP_PROOF : &str = "BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Generator of G1 in compressed form
+pub const BLS12_381_G1_GENERATOR : [u8; BLS12_381_PUBKEY_LEN] = [
+    151, 241, 211, 167, 49, 151, 215, 148, 38, 149, 99, 140, 79, 169, 172, 15, 195, 104, 140, 79,
+    151, 116, 185, 5, 161, 78, 58, 63, 23, 27, 172, 88, 108, 85, 232, 63, 249, 122, 26, 239, 251,
+    58, 240, 10, 219, 34, 198, 187,
+];
+
+
+/// Verify a signature or an aggregate of signatures of every given public key over the same message.
+/// Both the keys and the signatures are passed concatenated and are aggregated when there is more than one
+#[cfg(any(feature = "native", feature = "bls12_381"))]
+pub fn bls12_381_verify(
+    message: &[u8],
+    signatures: &[u8],
+    pubkeys: &[u8],
+    dst: &[u8],
+) -> Result<bool, saa_common::AuthError> {
+    use cosmwasm_crypto::{
+        bls12_381_aggregate_g1, bls12_381_aggregate_g2, bls12_381_hash_to_g2,
+        bls12_381_pairing_equality, HashFunction
+    };
+    let crypto_err = |e: cosmwasm_crypto::CryptoError| saa_common::AuthError::Crypto(e.to_string());
+    let pubkey = match pubkeys.len() {
+        BLS12_381_PUBKEY_LEN => pubkeys.try_into().unwrap(),
+        _ => bls12_381_aggregate_g1(pubkeys).map_err(crypto_err)?,
+    };
+    let signature = match signatures.len() {
+        BLS12_381_SIGNATURE_LEN => signatures.try_into().unwrap(),
+        _ => bls12_381_aggregate_g2(signatures).map_err(crypto_err)?,
+    };
+    let hashed = bls12_381_hash_to_g2(HashFunction::Sha256, message, dst);
+    bls12_381_pairing_equality(&BLS12_381_G1_GENERATOR, &signature, &pubkey, &hashed)
+        .map_err(crypto_err)
+}
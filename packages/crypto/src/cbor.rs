@@ -0,0 +1,293 @@
+use saa_common::{AuthError, String, ToString, Vec, format};
+
+
+// Minimal CBOR (RFC 8949) reader and writer covering the subset used by COSE and WebAuthn.
+// Only definite lengths are supported which is what the canonical encoding requires anyway
+
+const MAX_DEPTH: usize = 16;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CborValue {
+    Unsigned(u64),
+    /// Negative integer stored as `n` where the actual value is `-1 - n`
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    Map(Vec<(CborValue, CborValue)>),
+    Tag(u64, Box<CborValue>),
+    Bool(bool),
+    Null,
+    Undefined,
+    /// Floating point number kept as its additional info (width) and raw bits
+    Float(u8, u64),
+}
+
+
+fn error(reason: &str) -> AuthError {
+    AuthError::Convertation(format!("CBOR: {}", reason))
+}
+
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+
+impl<'a> Reader<'a> {
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AuthError> {
+        let end = self.pos.checked_add(len).ok_or_else(|| error("length overflow"))?;
+        let slice = self.data.get(self.pos..end).ok_or_else(|| error("unexpected end of data"))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn argument(&mut self, info: u8) -> Result<u64, AuthError> {
+        Ok(match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            31 => return Err(error("indefinite lengths are not supported")),
+            _ => return Err(error("reserved additional information")),
+        })
+    }
+
+    fn length(&mut self, info: u8) -> Result<usize, AuthError> {
+        let len = self.argument(info)?;
+        // every item takes at least one byte so anything longer than the rest of the data is invalid
+        if len > (self.data.len() - self.pos) as u64 {
+            return Err(error("unexpected end of data"));
+        }
+        Ok(len as usize)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<CborValue, AuthError> {
+        if depth > MAX_DEPTH {
+            return Err(error("nesting is too deep"));
+        }
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+
+        Ok(match major {
+            0 => CborValue::Unsigned(self.argument(info)?),
+            1 => CborValue::Negative(self.argument(info)?),
+            2 => {
+                let len = self.length(info)?;
+                CborValue::Bytes(self.take(len)?.to_vec())
+            },
+            3 => {
+                let len = self.length(info)?;
+                let text = core::str::from_utf8(self.take(len)?)
+                    .map_err(|_| error("invalid utf-8 in text string"))?;
+                CborValue::Text(text.to_string())
+            },
+            4 => {
+                let len = self.length(info)?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.value(depth + 1)?);
+                }
+                CborValue::Array(items)
+            },
+            5 => {
+                let len = self.length(info)?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let key = self.value(depth + 1)?;
+                    let value = self.value(depth + 1)?;
+                    entries.push((key, value));
+                }
+                CborValue::Map(entries)
+            },
+            6 => {
+                let tag = self.argument(info)?;
+                CborValue::Tag(tag, Box::new(self.value(depth + 1)?))
+            },
+            _ => match info {
+                20 => CborValue::Bool(false),
+                21 => CborValue::Bool(true),
+                22 => CborValue::Null,
+                23 => CborValue::Undefined,
+                25..=27 => CborValue::Float(info, self.argument(info)?),
+                _ => return Err(error("unsupported simple value")),
+            }
+        })
+    }
+}
+
+
+
+/// Decode a single CBOR item from the beginning of the data and return it
+/// together with the number of bytes it occupied
+pub fn decode_prefix(data: &[u8]) -> Result<(CborValue, usize), AuthError> {
+    let mut reader = Reader { data, pos: 0 };
+    let value = reader.value(0)?;
+    Ok((value, reader.pos))
+}
+
+
+/// Decode data that must consist of exactly one CBOR item
+pub fn from_slice(data: &[u8]) -> Result<CborValue, AuthError> {
+    let (value, read) = decode_prefix(data)?;
+    if read != data.len() {
+        return Err(error("trailing bytes after the item"));
+    }
+    Ok(value)
+}
+
+
+
+fn write_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    if arg < 24 {
+        out.push(major | arg as u8);
+    } else if arg <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(arg as u8);
+    } else if arg <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(arg as u16).to_be_bytes());
+    } else if arg <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(arg as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&arg.to_be_bytes());
+    }
+}
+
+
+fn write_value(out: &mut Vec<u8>, value: &CborValue) {
+    match value {
+        CborValue::Unsigned(n) => write_head(out, 0, *n),
+        CborValue::Negative(n) => write_head(out, 1, *n),
+        CborValue::Bytes(b) => {
+            write_head(out, 2, b.len() as u64);
+            out.extend_from_slice(b);
+        },
+        CborValue::Text(t) => {
+            write_head(out, 3, t.len() as u64);
+            out.extend_from_slice(t.as_bytes());
+        },
+        CborValue::Array(items) => {
+            write_head(out, 4, items.len() as u64);
+            items.iter().for_each(|i| write_value(out, i));
+        },
+        CborValue::Map(entries) => {
+            write_head(out, 5, entries.len() as u64);
+            entries.iter().for_each(|(k, v)| {
+                write_value(out, k);
+                write_value(out, v);
+            });
+        },
+        CborValue::Tag(tag, inner) => {
+            write_head(out, 6, *tag);
+            write_value(out, inner);
+        },
+        CborValue::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        CborValue::Null => out.push(0xf6),
+        CborValue::Undefined => out.push(0xf7),
+        CborValue::Float(width, bits) => {
+            out.push(0xe0 | width);
+            match width {
+                25 => out.extend_from_slice(&(*bits as u16).to_be_bytes()),
+                26 => out.extend_from_slice(&(*bits as u32).to_be_bytes()),
+                _ => out.extend_from_slice(&bits.to_be_bytes()),
+            }
+        }
+    }
+}
+
+
+/// Encode a value using definite lengths and the shortest possible heads
+pub fn to_vec(value: &CborValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(&mut out, value);
+    out
+}
+
+
+
+impl CborValue {
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            CborValue::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            CborValue::Text(t) => Some(t.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[CborValue]> {
+        match self {
+            CborValue::Array(a) => Some(a.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(CborValue, CborValue)]> {
+        match self {
+            CborValue::Map(m) => Some(m.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CborValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Integer value of both major types 0 and 1
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            CborValue::Unsigned(n) => Some(*n as i128),
+            CborValue::Negative(n) => Some(-1 - *n as i128),
+            _ => None,
+        }
+    }
+
+    /// Strip a semantic tag if present
+    pub fn untagged(&self) -> &CborValue {
+        match self {
+            CborValue::Tag(_, inner) => inner.untagged(),
+            other => other,
+        }
+    }
+
+    /// Look up a value in a map by an integer label as used by COSE
+    pub fn get_int(&self, label: i128) -> Option<&CborValue> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_int() == Some(label))
+            .map(|(_, v)| v)
+    }
+
+    /// Look up a value in a map by a text label
+    pub fn get_text(&self, label: &str) -> Option<&CborValue> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(label))
+            .map(|(_, v)| v)
+    }
+
+    pub fn int(value: i128) -> Self {
+        if value < 0 {
+            CborValue::Negative((-1 - value) as u64)
+        } else {
+            CborValue::Unsigned(value as u64)
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use saa_common::{AuthError, ToString, format};
+use saa_schema::saa_type;
+
+
+// Minimal ASN.1 DER reader for the structures that show up next to signatures:
+// ECDSA signatures and the parts of X.509 certificates needed to get to the public key
+
+pub const TAG_INTEGER      : u8 = 0x02;
+pub const TAG_BIT_STRING   : u8 = 0x03;
+pub const TAG_OCTET_STRING : u8 = 0x04;
+pub const TAG_OID          : u8 = 0x06;
+pub const TAG_SEQUENCE     : u8 = 0x30;
+
+
+fn error(reason: &str) -> AuthError {
+    AuthError::Convertation(format!("DER: {}", reason))
+}
+
+
+/// Read a single TLV from the beginning of the data and
+/// return its tag, content and the remaining data
+pub fn read_tlv(data: &[u8]) -> Result<(u8, &[u8], &[u8]), AuthError> {
+    let (&tag, rest) = data.split_first().ok_or_else(|| error("unexpected end of data"))?;
+    if tag & 0x1f == 0x1f {
+        return Err(error("high tag numbers are not supported"));
+    }
+    let (&first, rest) = rest.split_first().ok_or_else(|| error("unexpected end of data"))?;
+    let (len, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return Err(error("invalid length"));
+        }
+        let len = rest[..count].iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        if len < 0x80 || rest[0] == 0 {
+            return Err(error("non minimal length encoding"));
+        }
+        (len, &rest[count..])
+    };
+    if rest.len() < len {
+        return Err(error("unexpected end of data"));
+    }
+    Ok((tag, &rest[..len], &rest[len..]))
+}
+
+
+/// Same as `read_tlv` but also checks the tag
+pub fn expect_tlv(data: &[u8], expected: u8) -> Result<(&[u8], &[u8]), AuthError> {
+    let (tag, content, rest) = read_tlv(data)?;
+    if tag != expected {
+        return Err(error("unexpected tag"));
+    }
+    Ok((content, rest))
+}
+
+
+/// Strip the sign padding of a positive integer and left pad it to 32 bytes
+fn integer_to_scalar(int: &[u8]) -> Result<[u8; 32], AuthError> {
+    if int.is_empty() || int[0] & 0x80 != 0 {
+        return Err(error("ECDSA signature values must be positive integers"));
+    }
+    let int = if int.len() > 1 && int[0] == 0 {
+        if int[1] & 0x80 == 0 {
+            return Err(error("non minimal integer encoding"));
+        }
+        &int[1..]
+    } else {
+        int
+    };
+    if int.len() > 32 {
+        return Err(error("ECDSA signature value is too large"));
+    }
+    let mut scalar = [0u8; 32];
+    scalar[32 - int.len()..].copy_from_slice(int);
+    Ok(scalar)
+}
+
+
+/// Convert an ASN.1 DER encoded ECDSA signature `SEQUENCE { r INTEGER, s INTEGER }`
+/// into the compact `r || s` form of 64 bytes
+pub fn ecdsa_signature_to_raw(der: &[u8]) -> Result<[u8; 64], AuthError> {
+    let (seq, rest) = expect_tlv(der, TAG_SEQUENCE)?;
+    if !rest.is_empty() {
+        return Err(error("trailing bytes after the signature"));
+    }
+    let (r, seq) = expect_tlv(seq, TAG_INTEGER)?;
+    let (s, seq) = expect_tlv(seq, TAG_INTEGER)?;
+    if !seq.is_empty() {
+        return Err(error("unexpected elements in the signature"));
+    }
+    let mut raw = [0u8; 64];
+    raw[..32].copy_from_slice(&integer_to_scalar(r)?);
+    raw[32..].copy_from_slice(&integer_to_scalar(s)?);
+    Ok(raw)
+}
+
+
+/// Extract `SubjectPublicKeyInfo` of a DER encoded X.509 certificate and return
+/// the DER encoded algorithm identifier and the bytes of the public key
+pub fn certificate_public_key(cert: &[u8]) -> Result<(&[u8], &[u8]), AuthError> {
+    let (cert, _) = expect_tlv(cert, TAG_SEQUENCE)?;
+    let (tbs, _) = expect_tlv(cert, TAG_SEQUENCE)?;
+    let mut rest = tbs;
+    // optional explicit [0] version
+    let (tag, _, after) = read_tlv(rest)?;
+    if tag == 0xa0 {
+        rest = after;
+    }
+    // serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        rest = read_tlv(rest)?.2;
+    }
+    let (spki, _) = expect_tlv(rest, TAG_SEQUENCE)?;
+    let (algorithm, spki) = expect_tlv(spki, TAG_SEQUENCE)?;
+    let (key, _) = expect_tlv(spki, TAG_BIT_STRING)?;
+    match key.split_first() {
+        Some((0, key)) => Ok((algorithm, key)),
+        _ => Err(error("public key bit string must not have unused bits")),
+    }
+}
+
+
+
+/// Order of the secp256k1 curve
+pub const SECP256K1_ORDER : [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Order of the secp256r1 (P-256) curve
+pub const SECP256R1_ORDER : [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+
+/// Big endian subtraction of 256 bit numbers where `a >= b`
+fn sub_256(a: &[u8; 32], b: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = if diff < 0 { diff += 256; 1 } else { 0 };
+        out[i] = diff as u8;
+    }
+    out
+}
+
+
+/// Whether `s` of a compact signature is in the upper half of the curve order
+pub fn is_high_s(signature: &[u8; 64], order: &[u8; 32]) -> bool {
+    // floor(n / 2) computed by shifting the order one bit to the right
+    let mut half = [0u8; 32];
+    for i in 0..32 {
+        half[i] = (order[i] >> 1) | if i > 0 { order[i - 1] << 7 } else { 0 };
+    }
+    signature[32..] > half[..] && signature[32..] < order[..]
+}
+
+
+/// Replace `s` of a compact signature with `n - s` when it is in the upper half of the curve order
+pub fn normalize_s(signature: &mut [u8; 64], order: &[u8; 32]) {
+    if is_high_s(signature, order) {
+        let low = sub_256(order, &signature[32..]);
+        signature[32..].copy_from_slice(&low);
+    }
+}
+
+
+/// Treatment of ECDSA signatures with `s` in the upper half of the curve order. Every `(r, s)`
+/// signature has a valid `(r, n - s)` twin so allowing both makes the signatures malleable
+#[saa_type]
+#[derive(Copy)]
+pub enum HighSPolicy {
+    /// Fail on high S signatures before they reach the verifier
+    Reject,
+    /// Replace high S with its low S twin before the verification
+    Normalize,
+    /// Pass the signature to the backend as it is. All of the backends accept high S signatures
+    /// so only signatures that are malleable in the way the chain itself allows are verified
+    Accept,
+}
+
+
+impl HighSPolicy {
+    /// Apply the policy to a compact signature and return whether it has been normalized
+    pub fn apply(&self, signature: &mut [u8; 64], order: &[u8; 32]) -> Result<bool, AuthError> {
+        if !is_high_s(signature, order) {
+            return Ok(false);
+        }
+        match self {
+            Self::Reject => Err(AuthError::Signature("High S signatures are not allowed".to_string())),
+            Self::Normalize => {
+                normalize_s(signature, order);
+                Ok(true)
+            },
+            Self::Accept => Ok(false),
+        }
+    }
+}
+
+
+/// Accept an ECDSA signature in either DER or compact `r || s` encoding and
+/// return its compact form with `s` normalized to the lower half of the curve order
+pub fn compact_signature(signature: &[u8], order: &[u8; 32]) -> Result<[u8; 64], AuthError> {
+    compact_signature_with(signature, order, HighSPolicy::Normalize)
+}
+
+
+/// Compact form of an ECDSA signature in either DER or compact `r || s` encoding
+/// with the given policy applied to high S values
+pub fn compact_signature_with(
+    signature: &[u8], 
+    order: &[u8; 32], 
+    policy: HighSPolicy
+) -> Result<[u8; 64], AuthError> {
+    let mut compact = match signature.first() {
+        Some(&TAG_SEQUENCE) if signature.len() != 64 => ecdsa_signature_to_raw(signature)?,
+        // a 64 bytes long signature may only be DER if it fully parses as one
+        Some(&TAG_SEQUENCE) => ecdsa_signature_to_raw(signature)
+            .unwrap_or_else(|_| signature.try_into().unwrap()),
+        _ => signature.try_into()
+            .map_err(|_| AuthError::InvalidLength("Signature".to_string(), 64, signature.len() as u16))?,
+    };
+    policy.apply(&mut compact, order)?;
+    Ok(compact)
+}
@@ -0,0 +1,21 @@
+use saa_common::AuthError;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha512};
+
+
+/// Verify an Ed25519ph signature (RFC 8032) with an empty context. The message
+/// is the raw data that gets hashed with SHA-512 as part of the verification
+pub fn ed25519ph_verify(
+    message: &[u8],
+    signature: &[u8],
+    pubkey: &[u8],
+) -> Result<bool, AuthError> {
+    let pubkey : &[u8; 32] = pubkey.try_into()
+        .map_err(|_| AuthError::InvalidLength("Ed25519 public key".into(), 32, pubkey.len() as u16))?;
+    let key = VerifyingKey::from_bytes(pubkey)
+        .map_err(|_| AuthError::generic("Invalid Ed25519 public key"))?;
+    let signature = Signature::from_slice(signature)
+        .map_err(|_| AuthError::InvalidLength("Ed25519 signature".into(), 64, signature.len() as u16))?;
+    let prehashed = Sha512::new().chain_update(message);
+    Ok(key.verify_prehashed(prehashed, None, &signature).is_ok())
+}
@@ -1,8 +1,63 @@
-use {saa_common::Vec, sha2::{Digest, Sha256}};
+use {saa_common::{AuthError, CryptoApi, ToString, Vec, ensure}, sha2::{Digest, Sha256, Sha512_256}};
+use saa_schema::saa_type;
 # [cfg(feature = "cosmwasm")]
 use ripemd::Ripemd160;
-#[cfg(feature = "ethereum")]
+#[cfg(any(feature = "ethereum", feature = "digests"))]
 use tiny_keccak::{Hasher, Keccak};
+#[cfg(any(feature = "cardano", feature = "tezos", feature = "digests"))]
+use blake2::Blake2b;
+
+
+/// Digest of the message that a curve signature is made over
+#[saa_type]
+#[derive(Copy)]
+pub enum HashAlgorithm {
+    Sha256,
+    Keccak256,
+    Blake2b256,
+    /// SHA-512 truncated to 32 bytes
+    Sha512_256,
+    /// Message is already a 32 bytes long digest and is used as is
+    Prehashed,
+}
+
+
+impl HashAlgorithm {
+
+    /// Check that the message can be digested with the algorithm
+    pub fn validate(&self, msg: &[u8]) -> Result<(), AuthError> {
+        if let Self::Prehashed = self {
+            ensure!(msg.len() == 32, AuthError::InvalidLength("Prehashed message".to_string(), 32, msg.len() as u16));
+        }
+        Ok(())
+    }
+
+    pub fn digest(&self, msg: &[u8]) -> Result<Vec<u8>, AuthError> {
+        match self {
+            Self::Sha256 => Ok(sha256(msg)),
+            Self::Sha512_256 => Ok(sha512_256(msg).to_vec()),
+            #[cfg(any(feature = "ethereum", feature = "digests"))]
+            Self::Keccak256 => Ok(keccak256(msg).to_vec()),
+            #[cfg(any(feature = "tezos", feature = "digests"))]
+            Self::Blake2b256 => Ok(blake2b_256(msg).to_vec()),
+            Self::Prehashed => {
+                self.validate(msg)?;
+                Ok(msg.to_vec())
+            },
+            #[allow(unreachable_patterns)]
+            _ => Err(AuthError::generic("Hash algorithm requires `digests` feature to be enabled")),
+        }
+    }
+
+    /// Same as `digest` but with SHA-256 and Keccak-256 taken from the crypto backend
+    pub fn digest_with(&self, api: &dyn CryptoApi, msg: &[u8]) -> Result<Vec<u8>, AuthError> {
+        match self {
+            Self::Sha256 => Ok(api.sha256(msg)?.to_vec()),
+            Self::Keccak256 => Ok(api.keccak256(msg)?.to_vec()),
+            _ => self.digest(msg),
+        }
+    }
+}
 
 
 pub fn sha256(msg: &[u8]) -> Vec<u8> {
@@ -18,11 +73,40 @@ pub fn ripemd160(bytes: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
-#[cfg(feature = "ethereum")]
+pub fn sha512_256(msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(msg);
+    hasher.finalize().into()
+}
+
+#[cfg(any(feature = "ethereum", feature = "digests"))]
 pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
     let mut output = [0u8; 32];
     let mut hasher = Keccak::v256();
     hasher.update(bytes);
     hasher.finalize(&mut output);
     output
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "cardano")]
+pub fn blake2b_224(bytes: &[u8]) -> [u8; 28] {
+    let mut hasher = Blake2b::<blake2::digest::consts::U28>::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+
+#[cfg(feature = "tezos")]
+pub fn blake2b_160(bytes: &[u8]) -> [u8; 20] {
+    let mut hasher = Blake2b::<blake2::digest::consts::U20>::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+
+#[cfg(any(feature = "tezos", feature = "digests"))]
+pub fn blake2b_256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<blake2::digest::consts::U32>::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
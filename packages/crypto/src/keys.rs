@@ -0,0 +1,117 @@
+use saa_common::{AuthError, Binary, CredentialId, ToString};
+#[cfg(feature = "ethereum")]
+use {saa_common::{String, format}, crate::hashes::keccak256};
+
+
+/// Compressed SEC1 encoding of a point given in either compressed or uncompressed form.
+/// Only the encoding is checked and not whether the point is on the curve
+pub fn compress_sec1(pubkey: &[u8]) -> Result<[u8; 33], AuthError> {
+    match (pubkey.len(), pubkey.first()) {
+        (33, Some(0x02 | 0x03)) => Ok(pubkey.try_into().unwrap()),
+        (65, Some(0x04)) => {
+            let mut compressed = [0u8; 33];
+            compressed[0] = 0x02 | (pubkey[64] & 1);
+            compressed[1..].copy_from_slice(&pubkey[1..33]);
+            Ok(compressed)
+        },
+        _ => Err(AuthError::generic("Invalid SEC1 encoded public key")),
+    }
+}
+
+
+/// Check that a key is a well formed SEC1 encoding of a point in either form.
+/// Same as with compression the point itself isn't checked to be on the curve
+pub fn validate_sec1(pubkey: &[u8]) -> Result<(), AuthError> {
+    compress_sec1(pubkey).map(|_| ())
+}
+
+
+/// Credential id that `sec1_credential_id` gives to an id holding a base64 encoded SEC1 key.
+/// Allows to find and migrate credentials stored under uncompressed keys. Other ids are returned as is
+pub fn canonical_credential_id(id: &str) -> CredentialId {
+    match Binary::from_base64(id) {
+        Ok(key) if compress_sec1(&key).is_ok() => sec1_credential_id(&key),
+        _ => id.to_string(),
+    }
+}
+
+
+/// Credential id of a SEC1 encoded key. Uncompressed keys are identified by their compressed form
+/// so that both encodings of the same key resolve to one credential. Other data is used as is
+pub fn sec1_credential_id(pubkey: &[u8]) -> CredentialId {
+    match compress_sec1(pubkey) {
+        Ok(compressed) => Binary::from(compressed.as_slice()).to_string(),
+        Err(_) => Binary::from(pubkey).to_string(),
+    }
+}
+
+
+/// Check the length of an Ed25519 key. With `key_validation` feature also that it decodes to a curve point
+pub fn validate_ed25519(pubkey: &[u8]) -> Result<(), AuthError> {
+    saa_common::ensure!(pubkey.len() == 32, AuthError::InvalidLength("Ed25519 public key".to_string(), 32, pubkey.len() as u16));
+    #[cfg(feature = "key_validation")]
+    ed25519_dalek::VerifyingKey::from_bytes(pubkey.try_into().unwrap())
+        .map_err(|_| AuthError::generic("Invalid Ed25519 public key"))?;
+    Ok(())
+}
+
+
+/// Check the SEC1 encoding of a secp256k1 key. With `key_validation` feature also that it is a valid point
+pub fn secp256k1_validate(pubkey: &[u8]) -> Result<(), AuthError> {
+    // other encodings that the curve crate accepts, e.g. compact points, aren't allowed
+    validate_sec1(pubkey)?;
+    #[cfg(feature = "key_validation")]
+    secp256k1_decompress(pubkey)?;
+    Ok(())
+}
+
+
+/// Check that a SEC1 encoded key is a valid point of secp256r1
+#[cfg(feature = "p256")]
+pub fn secp256r1_validate(pubkey: &[u8]) -> Result<(), AuthError> {
+    // other encodings that the curve crate accepts, e.g. compact points, aren't allowed
+    validate_sec1(pubkey)?;
+    secp256r1_decompress(pubkey).map(|_| ())
+}
+
+
+/// Uncompressed SEC1 encoding of a secp256r1 point
+#[cfg(feature = "p256")]
+pub fn secp256r1_decompress(pubkey: &[u8]) -> Result<[u8; 65], AuthError> {
+    use p256::elliptic_curve::sec1::ToEncodedPoint;
+    let key = p256::PublicKey::from_sec1_bytes(pubkey)
+        .map_err(|_| AuthError::generic("Invalid secp256r1 public key"))?;
+    Ok(key.to_encoded_point(false).as_bytes().try_into().unwrap())
+}
+
+
+/// Uncompressed SEC1 encoding of a secp256k1 point
+#[cfg(feature = "secp256k1")]
+pub fn secp256k1_decompress(pubkey: &[u8]) -> Result<[u8; 65], AuthError> {
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    let key = k256::PublicKey::from_sec1_bytes(pubkey)
+        .map_err(|_| AuthError::generic("Invalid secp256k1 public key"))?;
+    Ok(key.to_encoded_point(false).as_bytes().try_into().unwrap())
+}
+
+
+/// EIP-55 mixed case hex encoding of 20 bytes of an account
+#[cfg(feature = "ethereum")]
+pub fn to_checksum_address(account: &[u8]) -> String {
+    let hex : String = account.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = keccak256(hex.as_bytes());
+    let mut address = String::from("0x");
+    for (i, c) in hex.chars().enumerate() {
+        let nibble = if i.is_multiple_of(2) { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        address.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+    }
+    address
+}
+
+
+/// Checksummed 0x address of a secp256k1 key: last 20 bytes of keccak256 of the uncompressed point
+#[cfg(all(feature = "ethereum", feature = "secp256k1"))]
+pub fn evm_address(pubkey: &[u8]) -> Result<String, AuthError> {
+    let key = secp256k1_decompress(pubkey)?;
+    Ok(to_checksum_address(&keccak256(&key[1..])[12..]))
+}
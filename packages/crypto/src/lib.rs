@@ -1,7 +1,17 @@
 pub mod hashes;
+pub mod keys;
+pub mod bls12_381;
+mod backends;
+pub use backends::*;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "der")]
+pub mod der;
 pub use hashes::sha256;
 use saa_common::cfg_mod_use;
 
 cfg_mod_use!("native", native);
 cfg_mod_use!("cosmwasm", wasm);
-cfg_mod_use!("secp256r1", secp256r1);
\ No newline at end of file
+cfg_mod_use!("secp256r1", secp256r1);
+cfg_mod_use!("rsa", rsassa);
+cfg_mod_use!("ed25519ph", ed25519ph);
\ No newline at end of file
@@ -1,6 +1,14 @@
 
-pub use cosmwasm_crypto::{secp256k1_verify, ed25519_verify};
-#[cfg(feature = "ethereum")]
-pub use cosmwasm_crypto::{secp256k1_recover_pubkey};
+pub use cosmwasm_crypto::{secp256k1_verify, secp256k1_recover_pubkey, ed25519_verify};
 #[cfg(not(feature = "secp256r1"))]
-pub use cosmwasm_crypto::secp256r1_verify;
\ No newline at end of file
+pub use cosmwasm_crypto::secp256r1_verify;
+
+
+/// Verify multiple ed25519 signatures at once with random coefficients taken from the OS
+pub fn ed25519_batch_verify(
+    messages: &[&[u8]],
+    signatures: &[&[u8]],
+    public_keys: &[&[u8]],
+) -> Result<bool, saa_common::AuthError> {
+    Ok(cosmwasm_crypto::ed25519_batch_verify(&mut rand_core::OsRng, messages, signatures, public_keys)?)
+}
@@ -0,0 +1,134 @@
+use saa_common::{AuthError, ToString, Vec};
+use saa_schema::saa_type;
+use rsa::{
+    pkcs1::DecodeRsaPublicKey, pkcs8::{DecodePublicKey, EncodePublicKey}, traits::PublicKeyParts,
+    BigUint, Pkcs1v15Sign, Pss, RsaPublicKey
+};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+
+/// Smallest modulus accepted for verification
+const RSA_MIN_BITS : usize = 2048;
+/// Largest modulus accepted for verification to keep the cost of exponentiation bounded
+const RSA_MAX_BITS : usize = 4096;
+
+
+
+/// RSA signature schemes named as JSON Web Algorithms
+#[saa_type]
+#[derive(Copy)]
+pub enum RsaScheme {
+    /// RSASSA-PKCS1-v1_5 with SHA-256
+    Rs256,
+    /// RSASSA-PKCS1-v1_5 with SHA-384
+    Rs384,
+    /// RSASSA-PKCS1-v1_5 with SHA-512
+    Rs512,
+    /// RSASSA-PSS with SHA-256, MGF1 with SHA-256 and a salt as long as the digest
+    Ps256,
+    /// RSASSA-PSS with SHA-384, MGF1 with SHA-384 and a salt as long as the digest
+    Ps384,
+    /// RSASSA-PSS with SHA-512, MGF1 with SHA-512 and a salt as long as the digest
+    Ps512,
+}
+
+
+impl RsaScheme {
+
+    pub fn digest(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Rs256 | Self::Ps256 => Sha256::digest(message).to_vec(),
+            Self::Rs384 | Self::Ps384 => Sha384::digest(message).to_vec(),
+            Self::Rs512 | Self::Ps512 => Sha512::digest(message).to_vec(),
+        }
+    }
+
+    /// Verify a signature over a digest of the scheme's hash function
+    pub fn verify_prehashed(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        key: &RsaPublicKey,
+    ) -> bool {
+        match self {
+            Self::Rs256 => key.verify(Pkcs1v15Sign::new::<Sha256>(), message_hash, signature),
+            Self::Rs384 => key.verify(Pkcs1v15Sign::new::<Sha384>(), message_hash, signature),
+            Self::Rs512 => key.verify(Pkcs1v15Sign::new::<Sha512>(), message_hash, signature),
+            Self::Ps256 => key.verify(Pss::new::<Sha256>(), message_hash, signature),
+            Self::Ps384 => key.verify(Pss::new::<Sha384>(), message_hash, signature),
+            Self::Ps512 => key.verify(Pss::new::<Sha512>(), message_hash, signature),
+        }.is_ok()
+    }
+}
+
+
+
+fn check_size(key: RsaPublicKey) -> Result<RsaPublicKey, AuthError> {
+    if !(RSA_MIN_BITS..=RSA_MAX_BITS).contains(&key.n().bits()) {
+        return Err(AuthError::generic("RSA modulus must be between 2048 and 4096 bits"));
+    }
+    Ok(key)
+}
+
+
+fn public_key(modulus: &[u8], exponent: &[u8]) -> Result<RsaPublicKey, AuthError> {
+    let key = RsaPublicKey::new(BigUint::from_bytes_be(modulus), BigUint::from_bytes_be(exponent))
+        .map_err(|e| AuthError::Crypto(e.to_string()))?;
+    check_size(key)
+}
+
+
+/// Decode a DER encoded public key given either as PKCS#1 `RSAPublicKey` or as X.509 `SubjectPublicKeyInfo`
+pub fn rsa_public_key_from_der(der: &[u8]) -> Result<RsaPublicKey, AuthError> {
+    let key = RsaPublicKey::from_pkcs1_der(der)
+        .or_else(|_| RsaPublicKey::from_public_key_der(der))
+        .map_err(|_| AuthError::generic("Invalid DER encoded RSA public key"))?;
+    check_size(key)
+}
+
+
+/// Re-encode a DER encoded public key given in either of the supported forms as X.509 `SubjectPublicKeyInfo`
+pub fn rsa_public_key_to_spki(der: &[u8]) -> Result<Vec<u8>, AuthError> {
+    let spki = rsa_public_key_from_der(der)?
+        .to_public_key_der()
+        .map_err(|e| AuthError::Crypto(e.to_string()))?;
+    Ok(spki.as_bytes().to_vec())
+}
+
+
+/// Verify a signature of the given scheme over a message with a DER encoded public key
+pub fn rsa_verify(
+    scheme: RsaScheme,
+    message: &[u8],
+    signature: &[u8],
+    pubkey: &[u8],
+) -> Result<bool, AuthError> {
+    let key = rsa_public_key_from_der(pubkey)?;
+    Ok(scheme.verify_prehashed(&scheme.digest(message), signature, &key))
+}
+
+
+/// Verify an RSASSA-PKCS1-v1_5 signature over a SHA-256 digest with a public key
+/// given as big endian modulus and exponent
+pub fn rsa_pkcs1v15_verify(
+    message_hash: &[u8],
+    signature: &[u8],
+    modulus: &[u8],
+    exponent: &[u8],
+) -> Result<bool, AuthError> {
+    let key = public_key(modulus, exponent)?;
+    Ok(RsaScheme::Rs256.verify_prehashed(message_hash, signature, &key))
+}
+
+
+/// Verify an RSASSA-PSS signature over a SHA-256 digest using MGF1 with SHA-256
+/// and a salt of 32 bytes with a public key given as big endian modulus and exponent
+pub fn rsa_pss_verify(
+    message_hash: &[u8],
+    signature: &[u8],
+    modulus: &[u8],
+    exponent: &[u8],
+) -> Result<bool, AuthError> {
+    let key = public_key(modulus, exponent)?;
+    Ok(RsaScheme::Ps256.verify_prehashed(message_hash, signature, &key))
+}
@@ -75,9 +75,8 @@ pub fn secp256r1_verify(
     let mut signature = Signature::from_bytes(&signature.into())
         .map_err(|e| AuthError::generic(e.to_string()))?;
 
-    // High-S signatures require normalization since our verification implementation
-    // rejects them by default. If we had a verifier that does not restrict to
-    // low-S only, this step was not needed.
+    // High-S signatures are accepted the same way as by the `Api` and native backends.
+    // Callers wanting a different behaviour apply `der::HighSPolicy` beforehand
     if let Some(normalized) = signature.normalize_s() {
         signature = normalized;
     }
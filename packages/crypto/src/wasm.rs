@@ -1,5 +1,6 @@
 use crate::{hashes::{ripemd160, sha256}};
-use saa_common::{AuthError, String};
+use saa_common::{AuthError, String, ToString, Vec};
+use crate::keys::compress_sec1;
 use bech32::{hrp::Hrp, Bech32};
 
 
@@ -18,4 +19,55 @@ pub fn pubkey_to_address(pubkey: &[u8], hrp: &str) -> Result<String, AuthError>
     let base32_addr = ripemd160(&sha256(pubkey));
     let account: String = bech32::encode::<Bech32>(Hrp::parse(hrp)?, &base32_addr)?;
     Ok(account)
-}
\ No newline at end of file
+}
+
+
+/// Proto message name of secp256r1 public keys that the Cosmos SDK hashes addresses with
+pub const SECP256R1_PUBKEY_TYPE : &str = "cosmos.crypto.secp256r1.PubKey";
+
+
+
+pub fn bech32_address(account: &[u8], hrp: &str) -> Result<String, AuthError> {
+    Ok(bech32::encode::<Bech32>(Hrp::parse(hrp)?, account)?)
+}
+
+
+/// Account bytes of a secp256k1 key in any SEC1 form: ripemd160(sha256(compressed key))
+pub fn secp256k1_account(pubkey: &[u8]) -> Result<Vec<u8>, AuthError> {
+    Ok(ripemd160(&sha256(&compress_sec1(pubkey)?)))
+}
+
+
+/// Account bytes of a secp256r1 key as derived by the Cosmos SDK with `address.Hash`:
+/// sha256(sha256("cosmos.crypto.secp256r1.PubKey") || compressed key)
+pub fn secp256r1_account(pubkey: &[u8]) -> Result<Vec<u8>, AuthError> {
+    Ok(sha256(&[sha256(SECP256R1_PUBKEY_TYPE.as_bytes()).as_slice(), &compress_sec1(pubkey)?].concat()))
+}
+
+
+/// Account bytes of an ed25519 key: first 20 bytes of sha256(key)
+pub fn ed25519_account(pubkey: &[u8]) -> Result<Vec<u8>, AuthError> {
+    saa_common::ensure!(
+        pubkey.len() == 32, 
+        AuthError::InvalidLength("Ed25519 public key".to_string(), 32, pubkey.len() as u16)
+    );
+    Ok(sha256(pubkey)[..20].to_vec())
+}
+
+
+/// Cosmos address of a secp256k1 key in any SEC1 form
+pub fn secp256k1_to_address(pubkey: &[u8], hrp: &str) -> Result<String, AuthError> {
+    bech32_address(&secp256k1_account(pubkey)?, hrp)
+}
+
+
+/// Cosmos address of a secp256r1 key in any SEC1 form
+pub fn secp256r1_to_address(pubkey: &[u8], hrp: &str) -> Result<String, AuthError> {
+    bech32_address(&secp256r1_account(pubkey)?, hrp)
+}
+
+
+/// Cosmos address of an ed25519 key
+pub fn ed25519_to_address(pubkey: &[u8], hrp: &str) -> Result<String, AuthError> {
+    bech32_address(&ed25519_account(pubkey)?, hrp)
+}
@@ -0,0 +1,115 @@
+use saa_schema::saa_type;
+use saa_common::{
+    CredentialId, CryptoApi, String, 
+    AuthError, Binary, ToString, Verifiable, ensure
+};
+use saa_crypto::bls12_381::{BLS12_381_DST_POP, BLS12_381_DST_POP_PROOF, BLS12_381_PUBKEY_LEN, BLS12_381_SIGNATURE_LEN};
+
+
+/// BLS12-381 credential with public keys in G1 and signatures in G2. Multiple keys and signatures
+/// over the same message are aggregated which lets a committee of signers control a single account.
+/// Aggregated keys are only accepted together with a proof of possession of every key to prevent rogue key attacks
+#[saa_type]
+pub struct Bls12381 {
+    /// Compressed G1 public key or concatenation of the keys of all the signers
+    pub pubkey:    Binary,
+    pub message:   Binary,
+    /// Compressed G2 signature, aggregated signature of all the signers or their concatenation
+    pub signature: Binary,
+    /// Domain separation tag of hashing the message to G2. Defaults to the one of the proof of possession scheme
+    pub dst:       Option<String>,
+    /// Concatenated proofs of possession of the keys in the same order. Required when there is more than one key
+    pub proofs:    Option<Binary>,
+}
+
+
+impl Bls12381 {
+
+    pub fn dst(&self) -> &str {
+        self.dst.as_deref().unwrap_or(BLS12_381_DST_POP)
+    }
+
+    /// Number of the keys that have signed the message
+    pub fn signers(&self) -> usize {
+        self.pubkey.len() / BLS12_381_PUBKEY_LEN
+    }
+
+    /// Check that every aggregated key has signed itself with the proof of possession scheme
+    fn verify_proofs(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        if self.signers() < 2 {
+            return Ok(());
+        }
+        let proofs = self.proofs.as_ref().ok_or_else(|| 
+            AuthError::MissingData("Proofs of possession of the aggregated keys".to_string())
+        )?;
+        ensure!(
+            proofs.len() == self.signers() * BLS12_381_SIGNATURE_LEN,
+            AuthError::generic("Aggregated keys require a proof of possession of every key")
+        );
+        self.pubkey
+            .chunks(BLS12_381_PUBKEY_LEN)
+            .zip(proofs.chunks(BLS12_381_SIGNATURE_LEN))
+            .try_for_each(|(key, proof)| {
+                let proven = api.bls12_381_verify(key, proof, key, BLS12_381_DST_POP_PROOF.as_bytes())?;
+                ensure!(proven, AuthError::Signature("Invalid proof of possession".to_string()));
+                Ok(())
+            })
+    }
+}
+
+
+impl Verifiable for Bls12381 {
+
+    fn id(&self) -> CredentialId {
+        self.pubkey.to_string()
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        ensure!(
+            !self.signature.is_empty() && !self.message.is_empty() && !self.pubkey.is_empty(),
+            AuthError::MissingData("Empty credential data".to_string())
+        );
+        ensure!(
+            self.pubkey.len().is_multiple_of(BLS12_381_PUBKEY_LEN),
+            AuthError::generic("Public keys must be compressed G1 points")
+        );
+        ensure!(
+            self.signature.len().is_multiple_of(BLS12_381_SIGNATURE_LEN),
+            AuthError::generic("Signatures must be compressed G2 points")
+        );
+        // either a single aggregated signature or one from every key
+        let signatures = self.signature.len() / BLS12_381_SIGNATURE_LEN;
+        ensure!(
+            signatures == 1 || signatures == self.signers(),
+            AuthError::generic("Number of signatures doesn't match the number of keys")
+        );
+        if self.signers() > 1 {
+            let proofs = self.proofs.as_ref().map(|p| p.len()).unwrap_or_default();
+            ensure!(
+                proofs == self.signers() * BLS12_381_SIGNATURE_LEN,
+                AuthError::generic("Aggregated keys require a proof of possession of every key")
+            );
+        }
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        self.verify_proofs(api)?;
+        let success = api.bls12_381_verify(
+            &self.message, &self.signature, &self.pubkey, self.dst().as_bytes()
+        )?;
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+}
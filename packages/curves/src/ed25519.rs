@@ -1,8 +1,22 @@
 use saa_schema::saa_type;
 use saa_common::{
-    CredentialId, 
-    AuthError, Binary, ToString, Verifiable, ensure
+    CredentialId,
+    AuthError, Binary, CryptoApi, ToString, Verifiable, ensure
 };
+use saa_crypto::hashes::HashAlgorithm;
+
+
+/// What exactly the signature of an Ed25519 credential is made over
+#[saa_type]
+#[derive(Copy)]
+pub enum Ed25519Mode {
+    /// Standard Ed25519 over the raw message as produced by most wallets and libraries
+    Pure,
+    /// Ed25519ph over the SHA-512 digest of the message with an empty context
+    Prehash,
+    /// Ed25519 over the digest of the message. SHA-256 unless `hash` says otherwise
+    Digest,
+}
 
 
 #[saa_type]
@@ -10,6 +24,46 @@ pub struct Ed25519 {
     pub pubkey:    Binary,
     pub message:   Binary,
     pub signature: Binary,
+    /// Signing mode. Defaults to `Digest` for backwards compatibility
+    pub mode:      Option<Ed25519Mode>,
+    /// Digest signed in `Digest` mode. Not allowed with the other modes
+    pub hash:      Option<HashAlgorithm>,
+}
+
+
+impl Ed25519 {
+
+    pub fn mode(&self) -> Ed25519Mode {
+        self.mode.unwrap_or(Ed25519Mode::Digest)
+    }
+
+    pub fn hash(&self) -> HashAlgorithm {
+        self.hash.unwrap_or(HashAlgorithm::Sha256)
+    }
+
+    /// Data passed to the regular Ed25519 verification
+    fn signed_data(&self, api: &dyn CryptoApi) -> Result<saa_common::Vec<u8>, AuthError> {
+        match self.mode() {
+            Ed25519Mode::Digest => self.hash().digest_with(api, &self.message),
+            _ => Ok(self.message.to_vec()),
+        }
+    }
+
+    /// Data that the signature is checked against when verified in a batch with other Ed25519 signatures.
+    /// Ed25519ph signatures can't be batched
+    pub fn batch_data(&self, api: &dyn CryptoApi) -> Option<Result<saa_common::Vec<u8>, AuthError>> {
+        match self.mode() {
+            Ed25519Mode::Prehash => None,
+            _ => Some(self.signed_data(api)),
+        }
+    }
+
+    fn verify_prehash(&self) -> Result<bool, AuthError> {
+        #[cfg(feature = "ed25519ph")]
+        return saa_crypto::ed25519ph_verify(&self.message, &self.signature, &self.pubkey);
+        #[cfg(not(feature = "ed25519ph"))]
+        Err(AuthError::generic("Ed25519ph signatures require `ed25519ph` feature to be enabled"))
+    }
 }
 
 
@@ -22,36 +76,39 @@ impl Verifiable for Ed25519 {
     fn validate(&self) -> Result<(), AuthError> {
         ensure!(
             self.signature.len() > 0 &&
-                self.message.len() > 0 && 
+                self.message.len() > 0 &&
                 self.pubkey.len() > 0,
             AuthError::MissingData("Empty credential data".to_string())
         );
+        saa_crypto::keys::validate_ed25519(&self.pubkey)?;
+        if !matches!(self.mode(), Ed25519Mode::Digest) {
+            ensure!(self.hash.is_none(), AuthError::generic("Hash algorithm can only be set in `digest` mode"));
+        }
+        self.hash().validate(&self.message)
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let success = match self.mode() {
+            Ed25519Mode::Prehash => self.verify_prehash()?,
+            _ => api.ed25519_verify(
+                &self.signed_data(api)?,
+                &self.signature,
+                &self.pubkey
+            )?,
+        };
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
         Ok(())
     }
 
     #[cfg(feature = "native")]
     fn verify(&self) -> Result<(), AuthError> {
-        let success = saa_crypto::ed25519_verify(
-            &saa_crypto::hashes::sha256(&self.message), 
-            &self.signature, 
-            &self.pubkey
-        )?;
-        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
-        Ok(())
+        self.verify_with(&saa_crypto::NativeCrypto)
     }
 
 
     #[cfg(feature = "cosmwasm")]
-    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> 
-        where Self: Clone
-    {
-        let success = api.ed25519_verify(
-            &saa_crypto::hashes::sha256(&self.message), 
-            &self.signature, 
-            &self.pubkey
-        )?;
-        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
-        Ok(())
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 
-}
\ No newline at end of file
+}
@@ -2,3 +2,7 @@
 pub mod ed25519;
 #[cfg(feature = "secp256k1")]
 pub mod secp256k1;
+#[cfg(feature = "bls12_381")]
+pub mod bls12_381;
+#[cfg(feature = "rsa")]
+pub mod rsa;
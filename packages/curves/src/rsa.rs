@@ -0,0 +1,64 @@
+use saa_schema::saa_type;
+use saa_common::{
+    CredentialId,
+    AuthError, Binary, ToString, Verifiable, ensure
+};
+use saa_crypto::RsaScheme;
+
+
+/// Signature made by an RSA key of 2048 to 4096 bits over raw data
+#[saa_type]
+pub struct Rsa {
+    /// DER encoded public key either as PKCS#1 `RSAPublicKey` or as X.509 `SubjectPublicKeyInfo`
+    pub pubkey:    Binary,
+    pub message:   Binary,
+    pub signature: Binary,
+    /// Signature scheme. Defaults to `Rs256`
+    pub scheme:    Option<RsaScheme>,
+}
+
+
+impl Rsa {
+
+    pub fn scheme(&self) -> RsaScheme {
+        self.scheme.unwrap_or(RsaScheme::Rs256)
+    }
+}
+
+
+impl Verifiable for Rsa {
+
+    /// Base64 of the key in `SubjectPublicKeyInfo` form so that both encodings of a key have the same id
+    fn id(&self) -> CredentialId {
+        saa_crypto::rsa_public_key_to_spki(&self.pubkey)
+            .map(|spki| Binary::from(spki).to_base64())
+            .unwrap_or_else(|_| self.pubkey.to_string())
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        ensure!(
+            !self.signature.is_empty() && !self.message.is_empty() && !self.pubkey.is_empty(),
+            AuthError::MissingData("Empty credential data".to_string())
+        );
+        saa_crypto::rsa_public_key_from_der(&self.pubkey)?;
+        Ok(())
+    }
+
+    /// Always verified inside of the contract with the Rust implementation and the given backend is ignored.
+    /// There is no host function for RSA in any environment
+    fn verify_with(&self, _: &dyn saa_common::CryptoApi) -> Result<(), AuthError> {
+        let success = saa_crypto::rsa_verify(self.scheme(), &self.message, &self.signature, &self.pubkey)?;
+        ensure!(success, AuthError::Signature("Signature verification failed".to_string()));
+        Ok(())
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+}
@@ -1,8 +1,9 @@
 use saa_common::{
     CredentialId,  
-    AuthError, Binary, ToString, Verifiable,
+    AuthError, Binary, CryptoApi, ToString, Verifiable,
     ensure
 };
+use saa_crypto::{der::HighSPolicy, hashes::HashAlgorithm};
 
 use saa_schema::saa_type;
 
@@ -12,14 +13,35 @@ pub struct Secp256k1 {
     pub pubkey:    Binary,
     pub message:   Binary,
     pub signature: Binary,
-    pub hrp:       Option<String>
+    pub hrp:       Option<String>,
+    /// Digest of the message that is signed. Defaults to `Sha256`
+    pub hash:      Option<HashAlgorithm>,
+    /// Treatment of high S signatures. Defaults to `Normalize`.
+    /// Chosen at registration and taken from the stored credential info by `build_credential`
+    pub high_s:    Option<HighSPolicy>,
+}
+
+
+impl Secp256k1 {
+    /// Signature in compact `r || s` form with the high S policy applied accepting DER encoded ones as well
+    fn compact_signature(&self) -> Result<[u8; 64], AuthError> {
+        saa_crypto::der::compact_signature_with(&self.signature, &saa_crypto::der::SECP256K1_ORDER, self.high_s())
+    }
+
+    pub fn hash(&self) -> HashAlgorithm {
+        self.hash.unwrap_or(HashAlgorithm::Sha256)
+    }
+
+    pub fn high_s(&self) -> HighSPolicy {
+        self.high_s.unwrap_or(HighSPolicy::Normalize)
+    }
 }
 
 
 impl Verifiable for Secp256k1 {
 
     fn id(&self) -> CredentialId {
-        self.pubkey.to_string()
+        saa_crypto::keys::sec1_credential_id(&self.pubkey)
     }
 
     fn hrp(&self) -> Option<String> {
@@ -32,29 +54,140 @@ impl Verifiable for Secp256k1 {
                 self.pubkey.len() > 0,
             AuthError::MissingData("Empty credential data".to_string())
         );
-        Ok(())
+        saa_crypto::keys::secp256k1_validate(&self.pubkey)?;
+        self.hash().validate(&self.message)
     }
 
-    #[cfg(feature = "native")]
-    fn verify(&self) -> Result<(), AuthError> {
-        let res = saa_crypto::secp256k1_verify(
-            &saa_crypto::hashes::sha256(&self.message), 
-            &self.signature, 
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let res = api.secp256k1_verify(
+            &self.hash().digest_with(api, &self.message)?, 
+            &self.compact_signature()?, 
             &self.pubkey
         )?;
         ensure!(res, AuthError::Signature("Signature verification failed".to_string()));
         Ok(())
     }
 
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
 
     #[cfg(feature = "cosmwasm")]
     fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
-        let res = api.secp256k1_verify(
-            &saa_crypto::hashes::sha256(&self.message), 
-            &self.signature, 
-            &self.pubkey
-        )?;
-        ensure!(res, AuthError::Signature("Signature verification failed".to_string()));
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+}
+
+
+
+/// Secp256k1 credential that carries no public key and recovers it from the signature instead
+#[saa_type]
+pub struct Secp256k1Recoverable {
+    /// Compressed public key in base64 or a bech32 address of the signer
+    pub signer:    String,
+    pub message:   Binary,
+    /// 65 bytes of `r || s || v` where the recovery id `v` is either 0 / 1 or 27 / 28
+    pub signature: Binary,
+    /// Digest of the message that is signed. Defaults to `Sha256`
+    pub hash:      Option<HashAlgorithm>,
+    /// Treatment of high S signatures. Defaults to `Normalize`.
+    /// Chosen at registration and taken from the stored credential info by `build_credential`
+    pub high_s:    Option<HighSPolicy>,
+}
+
+
+impl Secp256k1Recoverable {
+
+    pub fn hash(&self) -> HashAlgorithm {
+        self.hash.unwrap_or(HashAlgorithm::Sha256)
+    }
+
+    pub fn high_s(&self) -> HighSPolicy {
+        self.high_s.unwrap_or(HighSPolicy::Normalize)
+    }
+
+    /// Compact `r || s` with the high S policy applied and the recovery id matching it
+    fn compact_signature(&self) -> Result<([u8; 64], u8), AuthError> {
+        // fails on signatures that are too short to be sliced
+        let param = self.recovery_param()?;
+        let mut compact : [u8; 64] = self.signature[..64].try_into().unwrap();
+        let flipped = self.high_s().apply(&mut compact, &saa_crypto::der::SECP256K1_ORDER)?;
+        Ok((compact, param ^ flipped as u8))
+    }
+
+    pub fn recovery_param(&self) -> Result<u8, AuthError> {
+        match self.signature.get(64) {
+            Some(v @ (0 | 1)) => Ok(*v),
+            Some(v @ (27 | 28)) => Ok(v - 27),
+            _ => Err(AuthError::RecoveryParam),
+        }
+    }
+
+    /// Public key of the signer if it is given as one and not as an address
+    pub fn signer_pubkey(&self) -> Option<[u8; 33]> {
+        Binary::from_base64(&self.signer).ok()
+            .and_then(|key| saa_crypto::keys::compress_sec1(&key).ok())
+    }
+
+    /// Check that the recovered key is the one of the signer or derives its address
+    fn check_recovered(&self, recovered: &[u8]) -> Result<(), AuthError> {
+        let recovered = saa_crypto::keys::compress_sec1(recovered)?;
+        let matches = match self.signer_pubkey() {
+            Some(pubkey) => pubkey == recovered,
+            #[cfg(feature = "cosmwasm")]
+            None => {
+                let hrp = saa_crypto::prefix_from_address(&self.signer);
+                saa_crypto::secp256k1_to_address(&recovered, &hrp)? == self.signer
+            },
+            #[cfg(not(feature = "cosmwasm"))]
+            None => return Err(AuthError::generic("Matching addresses requires `cosmwasm` feature to be enabled")),
+        };
+        ensure!(matches, AuthError::RecoveryMismatch);
         Ok(())
     }
 }
+
+
+impl Verifiable for Secp256k1Recoverable {
+
+    fn id(&self) -> CredentialId {
+        match self.signer_pubkey() {
+            Some(pubkey) => Binary::from(pubkey.as_slice()).to_string(),
+            None => self.signer.to_string(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), AuthError> {
+        ensure!(!self.signer.is_empty() && !self.message.is_empty(),
+            AuthError::MissingData("Empty credential data".to_string())
+        );
+        ensure!(self.signature.len() == 65,
+            AuthError::InvalidLength("Recoverable signature".to_string(), 65, self.signature.len() as u16)
+        );
+        self.recovery_param()?;
+        self.hash().validate(&self.message)
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let (signature, param) = self.compact_signature()?;
+        let recovered = api.secp256k1_recover_pubkey(
+            &self.hash().digest_with(api, &self.message)?,
+            &signature,
+            param
+        )?;
+        self.check_recovered(&recovered)
+    }
+
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
+
+    #[cfg(feature = "cosmwasm")]
+    fn verify_cosmwasm(&self, api: &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
+    }
+}
@@ -41,6 +41,18 @@ fn strum_enum(input: &DeriveInput, attr_args: &[NestedMeta]) -> proc_macro2::Tok
             strum(serialize_all = "snake_case", crate = "::saa_schema::strum")
         )]
         #[strum(serialize_all = "snake_case", crate = "::saa_schema::strum")]
+        #[cfg_attr(feature = "substrate", derive(::saa_schema::scale::Encode, ::saa_schema::scale::Decode))]
+        #[cfg_attr(feature = "substrate", codec(crate = ::saa_schema::scale))]
+        #[cfg_attr(all(feature = "std", feature = "substrate"), derive(::saa_schema::scale_info::TypeInfo))]
+        #[cfg_attr(all(feature = "std", feature = "substrate"), scale_info(crate = ::saa_schema::scale_info))]
+        #[cfg_attr(feature = "substrate", strum_discriminants(
+            derive(::saa_schema::scale::Encode, ::saa_schema::scale::Decode),
+            codec(crate = ::saa_schema::scale)
+        ))]
+        #[cfg_attr(all(feature = "std", feature = "substrate"), strum_discriminants(
+            derive(::saa_schema::scale_info::TypeInfo),
+            scale_info(crate = ::saa_schema::scale_info)
+        ))]
         #[allow(clippy::derive_partial_eq_without_eq)]
         #input
 
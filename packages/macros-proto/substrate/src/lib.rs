@@ -20,6 +20,8 @@ pub fn saa_type(
                     ::saa_schema::scale::Decode,
                 )]
                 #[cfg_attr(feature = "std", derive(::saa_schema::scale_info::TypeInfo))]
+                #[codec(crate = ::saa_schema::scale)]
+                #[cfg_attr(feature = "std", scale_info(crate = ::saa_schema::scale_info))]
                 #[allow(clippy::derive_partial_eq_without_eq)]
                 #input_ast
             }.into()
@@ -34,6 +36,8 @@ pub fn saa_type(
                     ::saa_schema::scale::Decode,
                 )]
                 #[cfg_attr(feature = "std", derive(::saa_schema::scale_info::TypeInfo))]
+                #[codec(crate = ::saa_schema::scale)]
+                #[cfg_attr(feature = "std", scale_info(crate = ::saa_schema::scale_info))]
                 #[allow(clippy::derive_partial_eq_without_eq)]
                 #input_ast
             }.into()
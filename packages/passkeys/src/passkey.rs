@@ -1,6 +1,16 @@
 mod client_data;
 mod credential;
+mod registration;
 
 pub mod utils;
+pub mod authenticator;
+pub mod cose;
+pub mod origin;
+pub mod extensions;
 pub use credential::{PasskeyCredential, PasskeyInfo};
-pub use client_data::{ClientData, ClientDataOtherKeys, PasskeyPayload};
\ No newline at end of file
+pub use authenticator::{AuthenticatorData, AuthenticatorOptions};
+pub use registration::{PasskeyRegistration, RegistrationOptions};
+pub use cose::PasskeyPublicKey;
+pub use origin::OriginPolicy;
+pub use extensions::{ExtensionOutputs, CredProtect, HmacSecretOutput, DevicePublicKey};
+pub use client_data::{ClientData, ClientDataOtherKeys, ClientDataValue, PasskeyPayload};
\ No newline at end of file
@@ -0,0 +1,163 @@
+use saa_common::{AuthError, String, ToString, Vec, ensure};
+use saa_crypto::{cbor, sha256};
+use saa_schema::saa_type;
+use super::extensions::ExtensionOutputs;
+
+
+/// User Present
+pub const FLAG_UP : u8 = 0x01;
+/// User Verified
+pub const FLAG_UV : u8 = 0x04;
+/// Backup Eligible
+pub const FLAG_BE : u8 = 0x08;
+/// Backup State
+pub const FLAG_BS : u8 = 0x10;
+/// Attested credential data included
+pub const FLAG_AT : u8 = 0x40;
+/// Extension data included
+pub const FLAG_ED : u8 = 0x80;
+
+/// Length of `rpIdHash`, `flags` and `signCount` that every authenticator data starts with
+pub const AUTH_DATA_MIN_LEN : usize = 37;
+
+
+
+/// Requirements that authenticator data of an assertion must satisfy
+#[saa_type]
+#[derive(Default)]
+pub struct AuthenticatorOptions {
+    /// Relying party id that `rpIdHash` must be the hash of
+    pub rp_id               :   Option<String>,
+    /// Require the user presence flag
+    pub user_presence       :   bool,
+    /// Require the user verification flag
+    pub user_verification   :   bool,
+    /// Last stored signature counter. A new non zero counter must be greater than it
+    pub sign_count          :   Option<u32>,
+}
+
+
+
+/// Credential data that authenticators attach to the authenticator data during registration
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttestedCredential {
+    /// Identifier of the authenticator model
+    pub aaguid          :   [u8; 16],
+    /// Raw bytes of the credential id
+    pub credential_id   :   Vec<u8>,
+    /// CBOR encoded `COSE_Key` of the credential
+    pub public_key      :   Vec<u8>,
+}
+
+
+/// Decoded webauthn authenticator data
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthenticatorData {
+    /// SHA-256 hash of the relying party id
+    pub rp_id_hash          :   [u8; 32],
+    /// Bit flags of the authenticator
+    pub flags               :   u8,
+    /// Signature counter
+    pub sign_count          :   u32,
+    /// Present only when `AT` flag is set
+    pub attested_credential :   Option<AttestedCredential>,
+    /// CBOR encoded extension outputs. Present only when `ED` flag is set
+    pub extensions          :   Option<Vec<u8>>,
+}
+
+
+
+impl AuthenticatorData {
+
+    pub fn parse(data: &[u8]) -> Result<Self, AuthError> {
+        ensure!(data.len() >= AUTH_DATA_MIN_LEN, AuthError::generic("Invalid authenticator data"));
+        let flags = data[32];
+        ensure!(
+            flags & FLAG_BS == 0 || flags & FLAG_BE != 0,
+            AuthError::generic("Backup state flag is set for a credential that isn't backup eligible")
+        );
+        let mut rest = &data[AUTH_DATA_MIN_LEN..];
+
+        let attested_credential = if flags & FLAG_AT != 0 {
+            ensure!(rest.len() >= 18, AuthError::generic("Invalid attested credential data"));
+            let id_len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+            ensure!(rest.len() >= 18 + id_len, AuthError::generic("Invalid attested credential data"));
+            let aaguid = rest[..16].try_into().unwrap();
+            let credential_id = rest[18..18 + id_len].to_vec();
+            rest = &rest[18 + id_len..];
+            let (_, key_len) = cbor::decode_prefix(rest)?;
+            let public_key = rest[..key_len].to_vec();
+            rest = &rest[key_len..];
+            Some(AttestedCredential { aaguid, credential_id, public_key })
+        } else {
+            None
+        };
+
+        let extensions = if flags & FLAG_ED != 0 {
+            let (_, ext_len) = cbor::decode_prefix(rest)?;
+            let extensions = rest[..ext_len].to_vec();
+            rest = &rest[ext_len..];
+            Some(extensions)
+        } else {
+            None
+        };
+        ensure!(rest.is_empty(), AuthError::generic("Trailing bytes after the authenticator data"));
+
+        Ok(Self {
+            rp_id_hash: data[..32].try_into().unwrap(),
+            flags,
+            sign_count: u32::from_be_bytes(data[33..37].try_into().unwrap()),
+            attested_credential,
+            extensions,
+        })
+    }
+
+
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_UP != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_UV != 0
+    }
+
+    pub fn backup_eligible(&self) -> bool {
+        self.flags & FLAG_BE != 0
+    }
+
+    pub fn backed_up(&self) -> bool {
+        self.flags & FLAG_BS != 0
+    }
+
+    /// Decode the extension outputs if the `ED` flag is set
+    pub fn extension_outputs(&self) -> Result<Option<ExtensionOutputs>, AuthError> {
+        self.extensions.as_deref().map(ExtensionOutputs::parse).transpose()
+    }
+
+
+    /// Check the authenticator data against the requirements of the relying party
+    pub fn check(&self, options: &AuthenticatorOptions) -> Result<(), AuthError> {
+        if let Some(rp_id) = options.rp_id.as_ref() {
+            ensure!(
+                self.rp_id_hash.as_slice() == sha256(rp_id.as_bytes()).as_slice(),
+                AuthError::generic("Relying party id hash doesn't match")
+            );
+        }
+        ensure!(
+            !options.user_presence || self.user_present(),
+            AuthError::generic("User presence flag is not set")
+        );
+        ensure!(
+            !options.user_verification || self.user_verified(),
+            AuthError::generic("User verification flag is not set")
+        );
+        if let Some(stored) = options.sign_count {
+            // authenticators that don't implement counters always report zero
+            ensure!(
+                (stored == 0 && self.sign_count == 0) || self.sign_count > stored,
+                AuthError::Unauthorized("Signature counter didn't increase. The authenticator might be cloned".to_string())
+            );
+        }
+        Ok(())
+    }
+}
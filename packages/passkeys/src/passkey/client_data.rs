@@ -1,11 +1,20 @@
 
-use saa_common::String;
+use core::fmt;
+use saa_common::{AuthError, Binary, String, ToString, Vec, format};
 use saa_schema::saa_type;
+use serde::{de, ser::{self, SerializeMap}, Deserialize, Deserializer, Serialize};
+#[cfg(feature = "cosmwasm")]
+use std::collections::BTreeMap;
+
+
+/// Members of clientDataJSON that are part of `ClientData` itself
+const REGULAR_KEYS : [&str; 4] = ["type", "challenge", "origin", "crossOrigin"];
 
 
 
 /// The client data object defined by the WebAuthn standard.
 #[saa_type(no_deny)]
+#[cfg_attr(not(feature = "cosmwasm"), derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct ClientData {
     /// Type of the client data. The contract expects "webauthn.get"
@@ -22,18 +31,43 @@ pub struct ClientData {
     #[serde(rename = "crossOrigin")]
     pub cross_origin: bool,
 
-    /// Injecting other keys into the client data
-    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    /// Additional members that follow the regular ones in the order they were emitted
+    #[serde(flatten, skip_serializing_if = "Option::is_none", deserialize_with = "non_empty_keys")]
     pub other_keys : Option<ClientDataOtherKeys>,
 }
 
 
 
 
-#[saa_type(no_deny)]
-#[non_exhaustive]
-pub struct ClientDataOtherKeys {
-    pub other_keys_can_be_added_here :  Option<String>,
+/// Additional members of clientDataJSON that browsers put after the regular ones,
+/// e.g. `other_keys_can_be_added_here` of Chrome, `topOrigin` or `androidPackageName`.
+/// Serialized as a JSON object that keeps the order of the members
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "cosmwasm", derive(saa_schema::schemars::JsonSchema))]
+#[cfg_attr(feature = "cosmwasm", schemars(crate = "::saa_schema::schemars"))]
+#[cfg_attr(feature = "substrate", derive(saa_schema::scale::Encode, saa_schema::scale::Decode))]
+#[cfg_attr(feature = "substrate", codec(crate = ::saa_schema::scale))]
+#[cfg_attr(all(feature = "std", feature = "substrate"), derive(saa_schema::scale_info::TypeInfo))]
+#[cfg_attr(all(feature = "std", feature = "substrate"), scale_info(crate = ::saa_schema::scale_info))]
+pub struct ClientDataOtherKeys(
+    #[cfg_attr(feature = "cosmwasm", schemars(with = "BTreeMap<String, ClientDataValue>"))]
+    Vec<(String, ClientDataValue)>
+);
+
+
+/// JSON value of an additional clientDataJSON member. Floating point numbers aren't supported
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "substrate", derive(saa_schema::scale::Encode, saa_schema::scale::Decode))]
+#[cfg_attr(feature = "substrate", codec(crate = ::saa_schema::scale))]
+#[cfg_attr(all(feature = "std", feature = "substrate"), derive(saa_schema::scale_info::TypeInfo))]
+#[cfg_attr(all(feature = "std", feature = "substrate"), scale_info(crate = ::saa_schema::scale_info))]
+pub enum ClientDataValue {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<ClientDataValue>),
+    Object(ClientDataOtherKeys),
 }
 
 
@@ -43,7 +77,31 @@ pub struct PasskeyPayload {
     /// client data other keys
     pub other_keys :  Option<ClientDataOtherKeys>,
     // reserved for future use
-    pub origin: Option<String>
+    pub origin: Option<String>,
+    /// Raw clientDataJSON bytes to be hashed verbatim instead of the re-serialized client data
+    pub client_data_json: Option<Binary>,
+    /// User handle returned by a discoverable passkey. Used for locating the stored passkey
+    /// and must be the same as the one it was registered with
+    pub user_handle: Option<String>,
+    /// Authenticator data of the assertion. The stored authenticator data is used when missing
+    /// which only works with authenticators that don't implement signature counters
+    pub authenticator_data: Option<Binary>,
+}
+
+
+
+/// Fields of a raw clientDataJSON that are checked during verification.
+/// Everything else is ignored and only covered by the hash
+#[derive(serde::Deserialize)]
+pub(crate) struct CollectedClientData {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub challenge: String,
+    pub origin: String,
+    #[serde(rename = "crossOrigin", default)]
+    pub cross_origin: Option<bool>,
+    #[serde(rename = "topOrigin", default)]
+    pub top_origin: Option<String>,
 }
 
 
@@ -69,11 +127,259 @@ impl ClientData {
 
 
 impl ClientDataOtherKeys {
+
+    /// Keys with the `other_keys_can_be_added_here` member that Chrome adds to clientDataJSON
     pub fn new(
         other_keys_can_be_added_here: Option<String>
     ) -> Self {
-        Self {
-            other_keys_can_be_added_here
+        let mut keys = Self::default();
+        if let Some(value) = other_keys_can_be_added_here {
+            keys.insert("other_keys_can_be_added_here", value);
         }
+        keys
+    }
+
+    /// Same as `insert` but consuming and returning the keys
+    pub fn with(mut self, key: impl ToString, value: impl Into<ClientDataValue>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Replace the value of an existing member in place or append a new one to the end
+    pub fn insert(&mut self, key: impl ToString, value: impl Into<ClientDataValue>) -> Option<ClientDataValue> {
+        let key = key.to_string();
+        let value = value.into();
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, old)) => Some(core::mem::replace(old, value)),
+            None => {
+                self.0.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ClientDataValue> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ClientDataValue)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+
+impl<K: ToString, V: Into<ClientDataValue>> FromIterator<(K, V)> for ClientDataOtherKeys {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut keys = Self::default();
+        for (k, v) in iter {
+            keys.insert(k, v);
+        }
+        keys
+    }
+}
+
+
+impl From<bool> for ClientDataValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for ClientDataValue {
+    fn from(value: i64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<&str> for ClientDataValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for ClientDataValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<ClientDataOtherKeys> for ClientDataValue {
+    fn from(value: ClientDataOtherKeys) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl<T: Into<ClientDataValue>> From<Vec<T>> for ClientDataValue {
+    fn from(value: Vec<T>) -> Self {
+        Self::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+
+
+/// Serializes as a JSON object with the members in their original order
+impl Serialize for ClientDataOtherKeys {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0.iter() {
+            if REGULAR_KEYS.contains(&key.as_str()) {
+                return Err(ser::Error::custom(format!("{} is a regular client data member", key)));
+            }
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+
+impl Serialize for ClientDataValue {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Number(n) => serializer.serialize_i64(*n),
+            Self::String(s) => serializer.serialize_str(s),
+            Self::Array(values) => serializer.collect_seq(values),
+            Self::Object(keys) => keys.serialize(serializer),
+        }
+    }
+}
+
+
+/// Deserializes from a JSON object keeping the order of the members
+impl<'de> Deserialize<'de> for ClientDataOtherKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(OtherKeysVisitor)
+    }
+}
+
+
+impl<'de> Deserialize<'de> for ClientDataValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+
+/// Described as any JSON value in the schema
+#[cfg(feature = "cosmwasm")]
+impl saa_schema::schemars::JsonSchema for ClientDataValue {
+    fn schema_name() -> String {
+        "ClientDataValue".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(_: &mut saa_schema::schemars::gen::SchemaGenerator) -> saa_schema::schemars::schema::Schema {
+        saa_schema::schemars::schema::Schema::Bool(true)
+    }
+}
+
+
+/// Treat an empty set of additional members the same way as their absence
+fn non_empty_keys<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<ClientDataOtherKeys>, D::Error> {
+    let keys = ClientDataOtherKeys::deserialize(deserializer)?;
+    Ok(if keys.is_empty() { None } else { Some(keys) })
+}
+
+
+
+struct OtherKeysVisitor;
+
+impl<'de> de::Visitor<'de> for OtherKeysVisitor {
+    type Value = ClientDataOtherKeys;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON object")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut keys = ClientDataOtherKeys::default();
+        while let Some((key, value)) = access.next_entry::<String, ClientDataValue>()? {
+            if keys.get(&key).is_some() {
+                return Err(de::Error::custom(format!("duplicate member {}", key)));
+            }
+            keys.0.push((key, value));
+        }
+        Ok(keys)
+    }
+}
+
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = ClientDataValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value without floating point numbers")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::Null)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::Number(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(ClientDataValue::Number)
+            .map_err(|_| E::custom("number is too large"))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(ClientDataValue::String(v))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = access.next_element()? {
+            values.push(value);
+        }
+        Ok(ClientDataValue::Array(values))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, access: A) -> Result<Self::Value, A::Error> {
+        OtherKeysVisitor.visit_map(access).map(ClientDataValue::Object)
+    }
+}
+
+
+
+impl CollectedClientData {
+    pub fn parse(data: &[u8]) -> Result<Self, AuthError> {
+        Ok(saa_common::from_json(data)?)
+    }
+
+    /// Check that the raw data carries the same values as the expected client data
+    pub fn matches(&self, expected: &ClientData) -> bool {
+        self.ty == expected.ty &&
+        self.challenge == expected.challenge &&
+        self.origin == expected.origin &&
+        self.cross_origin.unwrap_or(false) == expected.cross_origin
     }
 }
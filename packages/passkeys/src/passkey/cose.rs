@@ -0,0 +1,151 @@
+use saa_common::{AuthError, CryptoApi, ToString, Vec, ensure};
+use saa_crypto::cbor;
+
+
+/// ECDSA over P-256 with SHA-256
+pub const COSE_ALG_ES256 : i128 = -7;
+/// EdDSA over Ed25519
+pub const COSE_ALG_EDDSA : i128 = -8;
+/// RSASSA-PSS with SHA-256
+pub const COSE_ALG_PS256 : i128 = -37;
+/// RSASSA-PKCS1-v1_5 with SHA-256
+pub const COSE_ALG_RS256 : i128 = -257;
+
+const COSE_KTY_OKP : i128 = 1;
+const COSE_KTY_EC2 : i128 = 2;
+const COSE_KTY_RSA : i128 = 3;
+const COSE_CRV_P256 : i128 = 1;
+const COSE_CRV_ED25519 : i128 = 6;
+
+
+
+/// Public key of a passkey together with the algorithm it signs with
+#[derive(Clone, Debug, PartialEq)]
+pub enum PasskeyPublicKey {
+    /// SEC1 encoded P-256 point
+    Es256(Vec<u8>),
+    /// Raw 32 bytes of an Ed25519 key
+    EdDsa(Vec<u8>),
+    /// Big endian modulus and exponent of an RSA key used with PKCS#1 v1.5 padding
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+    /// Big endian modulus and exponent of an RSA key used with PSS padding
+    Ps256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+
+
+fn key_bytes<'a>(key: &'a cbor::CborValue, label: i128, name: &str) -> Result<&'a [u8], AuthError> {
+    key.get_int(label)
+        .and_then(|v| v.as_bytes())
+        .ok_or_else(|| AuthError::MissingData(saa_common::format!("Missing {} in COSE_Key", name)))
+}
+
+
+
+impl PasskeyPublicKey {
+
+    /// Decode a CBOR encoded `COSE_Key` of any supported algorithm
+    pub fn from_cose(data: &[u8]) -> Result<Self, AuthError> {
+        let key = cbor::from_slice(data)?;
+        let kty = key.get_int(1).and_then(|k| k.as_int());
+        let alg = key.get_int(3).and_then(|a| a.as_int())
+            .ok_or_else(|| AuthError::MissingData("Missing algorithm in COSE_Key".to_string()))?;
+        let crv = key.get_int(-1).and_then(|c| c.as_int());
+
+        match (kty, alg) {
+            (Some(COSE_KTY_EC2), COSE_ALG_ES256) => {
+                ensure!(crv == Some(COSE_CRV_P256), AuthError::generic("ES256 keys must be on P-256 curve"));
+                let (x, y) = (key_bytes(&key, -2, "x")?, key_bytes(&key, -3, "y")?);
+                ensure!(
+                    x.len() == 32 && y.len() == 32,
+                    AuthError::InvalidLength("COSE_Key coordinate".to_string(), 32, x.len().max(y.len()) as u16)
+                );
+                Ok(Self::Es256([&[0x04], x, y].concat()))
+            },
+            (Some(COSE_KTY_OKP), COSE_ALG_EDDSA) => {
+                ensure!(crv == Some(COSE_CRV_ED25519), AuthError::generic("Only Ed25519 curve is supported for EdDSA"));
+                let x = key_bytes(&key, -2, "x")?;
+                ensure!(x.len() == 32, AuthError::InvalidLength("Ed25519 public key".to_string(), 32, x.len() as u16));
+                Ok(Self::EdDsa(x.to_vec()))
+            },
+            (Some(COSE_KTY_RSA), COSE_ALG_RS256 | COSE_ALG_PS256) => {
+                let n = key_bytes(&key, -1, "modulus")?.to_vec();
+                let e = key_bytes(&key, -2, "exponent")?.to_vec();
+                Ok(if alg == COSE_ALG_RS256 { Self::Rs256 { n, e } } else { Self::Ps256 { n, e } })
+            },
+            _ => Err(AuthError::generic("Unsupported COSE key type or algorithm")),
+        }
+    }
+
+
+    /// Decode either a SEC1 encoded P-256 key used with ES256 or a CBOR encoded `COSE_Key`
+    pub fn decode(data: &[u8]) -> Result<Self, AuthError> {
+        match data.first() {
+            Some(0x02..=0x04) => Ok(Self::Es256(data.to_vec())),
+            // CBOR maps use major type 5
+            Some(b) if b >> 5 == 5 => Self::from_cose(data),
+            _ => Err(AuthError::generic("Passkey public key must be a SEC1 point or a COSE_Key")),
+        }
+    }
+
+
+    /// COSE identifier of the algorithm
+    pub fn algorithm(&self) -> i128 {
+        match self {
+            Self::Es256(_) => COSE_ALG_ES256,
+            Self::EdDsa(_) => COSE_ALG_EDDSA,
+            Self::Rs256 { .. } => COSE_ALG_RS256,
+            Self::Ps256 { .. } => COSE_ALG_PS256,
+        }
+    }
+
+
+    fn verify_rsa(&self, signed: &[u8], signature: &[u8]) -> Result<bool, AuthError> {
+        #[cfg(feature = "rsa")]
+        return match self {
+            Self::Rs256 { n, e } => saa_crypto::rsa_pkcs1v15_verify(&saa_crypto::sha256(signed), signature, n, e),
+            Self::Ps256 { n, e } => saa_crypto::rsa_pss_verify(&saa_crypto::sha256(signed), signature, n, e),
+            _ => Ok(false),
+        };
+        #[cfg(not(feature = "rsa"))]
+        {
+            let _ = (signed, signature);
+            Err(AuthError::generic("RSA passkeys require `rsa` feature to be enabled"))
+        }
+    }
+
+
+    /// Verify a signature over the data that hasn't been hashed yet with the primitives of the crypto backend.
+    /// RSA signatures are always verified in place
+    pub fn verify_with(&self, api: &dyn CryptoApi, signed: &[u8], signature: &[u8]) -> Result<bool, AuthError> {
+        use saa_crypto::der::{compact_signature, SECP256R1_ORDER};
+        match self {
+            Self::Es256(key) => api.secp256r1_verify(
+                &api.sha256(signed)?,
+                &compact_signature(signature, &SECP256R1_ORDER)?,
+                key
+            ),
+            Self::EdDsa(key) => api.ed25519_verify(signed, signature, key),
+            _ => self.verify_rsa(signed, signature),
+        }
+    }
+
+
+    /// Verify a signature over the data that hasn't been hashed yet
+    #[cfg(feature = "native")]
+    pub fn verify(&self, signed: &[u8], signature: &[u8]) -> Result<bool, AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto, signed, signature)
+    }
+
+
+    /// Same as `verify` but using the crypto API of the chain when possible
+    #[cfg(feature = "cosmwasm")]
+    pub fn verify_cosmwasm(
+        &self,
+        api: &dyn saa_common::wasm::Api,
+        signed: &[u8],
+        signature: &[u8]
+    ) -> Result<bool, AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api), signed, signature)
+    }
+}
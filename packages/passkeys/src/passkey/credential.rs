@@ -1,7 +1,13 @@
-use saa_common::{AuthError, Binary, CredentialId, String, Verifiable, ensure};
+use saa_common::{AuthError, Binary, CredentialId, CryptoApi, String, Verifiable, ensure};
 use saa_schema::saa_type;
 
-use super::client_data::ClientData;
+use super::{
+    client_data::{ClientData, CollectedClientData},
+    authenticator::{AuthenticatorData, AuthenticatorOptions},
+    origin::OriginPolicy,
+    extensions::ExtensionOutputs,
+};
+use super::cose::PasskeyPublicKey;
 
 // expand later after adding implementations for other platforms
 
@@ -12,17 +18,24 @@ use super::client_data::ClientData;
 pub struct PasskeyCredential {
     /// Passkey id
     pub id                   :       String,
-    /// Secp256r1 signature
+    /// Signature of the passkey. ECDSA signatures can be either DER encoded or compact
     pub signature            :       Binary,
     /// webauthn Authenticator data
     pub authenticator_data   :       Binary,
     /// Client data containg challenge, origin and type
     pub client_data          :       ClientData,
-    /// Optional user handle reserved for future use
+    /// Raw clientDataJSON bytes returned by the browser. When provided they are hashed verbatim
+    /// and must contain the same type, challenge, origin and crossOrigin as `client_data`
+    pub client_data_json     :       Option<Binary>,
+    /// User handle of a discoverable passkey that it was registered with
     pub user_handle          :       Option<String>,
     /// Public key is essential for verification but can be supplied on the backend / contract side
     /// and omitted by client. Must be set when going through the verification process.
+    /// Either a SEC1 encoded P-256 key or a CBOR encoded COSE_Key of any supported algorithm
     pub pubkey               :       Option<Binary>,
+    /// Requirements that the authenticator data must satisfy. Set on the backend / contract side
+    /// from the stored passkey info. Only the user presence flag is required when omitted
+    pub authenticator_options:       Option<AuthenticatorOptions>,
 }
 
 
@@ -33,13 +46,21 @@ pub struct PasskeyInfo {
     pub authenticator_data: Binary,
     /// Origin of the client where the passkey was created
     pub origin: String,
-    /// Secpk256r1 Public key used for verification 
+    /// Public key used for verification. Either a SEC1 encoded P-256 key used with ES256
+    /// or a CBOR encoded COSE_Key with its algorithm
     pub pubkey: Binary,
     // Flag to allow cross origin requests
     #[cfg_attr(feature = "cosmwasm", serde(rename = "crossOrigin"))]
     pub cross_origin: bool,
-    /// Optional user handle reserved for future use
+    /// User handle passed as `user.id` during registration. Allows to locate the passkey
+    /// in usernameless flows where the client doesn't know the credential id
     pub user_handle: Option<String>,
+    /// Additional origins and relying party ids the passkey may be used with.
+    /// Only the stored origin is allowed when missing
+    pub origin_policy: Option<OriginPolicy>,
+    /// Signature counter of the last verified assertion. Must be updated after every verification
+    /// with [`PasskeyInfo::update_sign_count`] to detect cloned authenticators
+    pub sign_count: Option<u32>,
 }
 
 
@@ -55,16 +76,142 @@ impl PasskeyCredential {
         Ok(binary.to_vec())
     }
 
-    #[cfg(any(feature = "cosmwasm", feature = "native"))]
-    fn message_digest(&self) -> Result<Vec<u8>, AuthError> {
-        let client_data_hash = saa_crypto::sha256(&saa_common::to_json_binary(&self.client_data)?);
-        let final_digest = saa_crypto::sha256(
-            &[self.authenticator_data.as_slice(), client_data_hash.as_slice()].concat()
-        );
-        Ok(final_digest)
+    /// Decode the authenticator data of the assertion
+    pub fn parsed_authenticator_data(&self) -> Result<AuthenticatorData, AuthError> {
+        AuthenticatorData::parse(&self.authenticator_data)
+    }
+
+    /// Decoded extension outputs of the assertion. Only meaningful after the credential
+    /// is verified since the authenticator data is covered by the signature
+    pub fn extension_outputs(&self) -> Result<Option<ExtensionOutputs>, AuthError> {
+        self.parsed_authenticator_data()?.extension_outputs()
+    }
+
+    /// Requirements to check the authenticator data against
+    pub fn effective_authenticator_options(&self) -> AuthenticatorOptions {
+        self.authenticator_options.clone().unwrap_or(AuthenticatorOptions {
+            user_presence: true,
+            ..Default::default()
+        })
+    }
+
+    /// Check the authenticator data against the requirements of the relying party
+    /// and return the decoded data to e.g. store the new signature counter
+    pub fn check_authenticator_data(&self, options: &AuthenticatorOptions) -> Result<AuthenticatorData, AuthError> {
+        let data = self.parsed_authenticator_data()?;
+        data.check(options)?;
+        Ok(data)
+    }
+
+    /// Check the authenticator data and verify the signature of the assertion.
+    /// Returns the decoded authenticator data whose `sign_count` must be stored by the caller,
+    /// e.g. with [`PasskeyInfo::update_sign_count`]
+    pub fn verify_assertion(&self, api: &dyn CryptoApi) -> Result<AuthenticatorData, AuthError> {
+        let data = self.check_authenticator_data(&self.effective_authenticator_options())?;
+        let res = self.public_key()?.verify_with(api, &self.signed_data()?, &self.signature)?;
+        ensure!(res, AuthError::Signature("Passkey Signature verification failed".to_string()));
+        Ok(data)
+    }
+
+    /// Check the origin, cross origin context and relying party of the assertion against the policy.
+    /// `topOrigin` is only known when the raw clientDataJSON is provided
+    pub fn check_origin(&self, policy: &OriginPolicy) -> Result<(), AuthError> {
+        let top_origin = match self.client_data_json.as_ref() {
+            Some(json) => CollectedClientData::parse(json)?.top_origin,
+            None => None,
+        };
+        policy.check(
+            &self.client_data.origin,
+            self.client_data.cross_origin,
+            top_origin.as_deref(),
+            &self.parsed_authenticator_data()?.rp_id_hash
+        )
+    }
+
+    /// Check that the raw clientDataJSON (if any) carries the expected values
+    fn check_client_data_json(&self) -> Result<(), AuthError> {
+        if let Some(json) = self.client_data_json.as_ref() {
+            let collected = CollectedClientData::parse(json)?;
+            ensure!(collected.matches(&self.client_data), AuthError::generic("Raw client data doesn't match the expected values"));
+        }
+        Ok(())
+    }
+
+    /// Data signed by the authenticator: authenticator data followed by the hash of the client data
+    fn signed_data(&self) -> Result<Vec<u8>, AuthError> {
+        self.check_client_data_json()?;
+        let client_data_hash = match self.client_data_json.as_ref() {
+            Some(json) => saa_crypto::sha256(json),
+            None => saa_crypto::sha256(&saa_common::to_json_binary(&self.client_data)?),
+        };
+        Ok([self.authenticator_data.as_slice(), client_data_hash.as_slice()].concat())
+    }
+
+    fn public_key(&self) -> Result<PasskeyPublicKey, AuthError> {
+        let pubkey = self.pubkey.as_ref()
+            .ok_or_else(|| AuthError::generic("Missing public key"))?;
+        PasskeyPublicKey::decode(pubkey)
+    }
+}
+
+impl PasskeyInfo {
+
+    /// Check the user handle returned together with an assertion against the stored one
+    pub fn check_user_handle(&self, returned: Option<&str>) -> Result<(), AuthError> {
+        if let Some(returned) = returned {
+            ensure!(
+                self.user_handle.as_deref() == Some(returned),
+                AuthError::Unauthorized("User handle doesn't match the passkey".to_string())
+            );
+        }
+        Ok(())
+    }
+
+    /// Requirements of the stored passkey that the authenticator data of assertions must satisfy
+    pub fn authenticator_options(&self) -> AuthenticatorOptions {
+        AuthenticatorOptions {
+            rp_id: None,
+            user_presence: true,
+            user_verification: false,
+            sign_count: self.sign_count,
+        }
+    }
+
+    /// Check `rpIdHash` of an assertion against the relying party ids of the origin policy
+    /// or against the one of the stored authenticator data when the policy has none
+    pub fn check_rp_id_hash(&self, rp_id_hash: &[u8]) -> Result<(), AuthError> {
+        let rp_ids = self.origin_policy.as_ref().map(|p| p.rp_ids.as_slice()).unwrap_or_default();
+        let allowed = match rp_ids.is_empty() {
+            true => AuthenticatorData::parse(&self.authenticator_data)?.rp_id_hash.as_slice() == rp_id_hash,
+            false => rp_ids.iter().any(|id| saa_crypto::sha256(id.as_bytes()).as_slice() == rp_id_hash),
+        };
+        ensure!(allowed, AuthError::generic("Relying party id hash doesn't match"));
+        Ok(())
+    }
+
+    /// Check the authenticator data of a verified assertion against the stored passkey
+    /// and store its signature counter. Returns the new counter
+    pub fn update_sign_count(&mut self, credential: &PasskeyCredential) -> Result<u32, AuthError> {
+        let data = credential.parsed_authenticator_data()?;
+        self.check_rp_id_hash(&data.rp_id_hash)?;
+        data.check(&self.authenticator_options())?;
+        self.sign_count = Some(data.sign_count);
+        Ok(data.sign_count)
+    }
+
+    /// Policy to check assertions against. The stored origin is always allowed
+    /// and cross origin requests are allowed if the passkey was registered with one
+    pub fn effective_origin_policy(&self) -> OriginPolicy {
+        let mut policy = self.origin_policy.clone().unwrap_or_default();
+        if !policy.origins.contains(&self.origin) {
+            policy.origins.insert(0, self.origin.clone());
+        }
+        policy.cross_origin |= self.cross_origin;
+        policy
     }
 }
 
+
 impl Verifiable for PasskeyCredential {
 
     fn id(&self) -> CredentialId {
@@ -72,48 +219,30 @@ impl Verifiable for PasskeyCredential {
     }
 
     fn validate(&self) -> Result<(), AuthError> {
-        ensure!(self.authenticator_data.len() >= 37, AuthError::generic("Invalid authenticator data"));
+        self.parsed_authenticator_data()?;
         ensure!(self.signature.len() > 0, AuthError::generic("Empty signature"));
         ensure!(self.client_data.challenge.len() > 0, AuthError::generic("Empty challenge"));
         ensure!(self.client_data.ty == "webauthn.get", AuthError::generic("Invalid client data type"));
         ensure!(self.pubkey.is_some(), AuthError::generic("Missing public key"));
         self.base64_message_bytes()?;
+        self.check_client_data_json()?;
+        Ok(())
+    }
+
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        self.verify_assertion(api)?;
         Ok(())
     }
 
     #[cfg(feature = "native")]
     fn verify(&self) -> Result<(), AuthError> {
-        let res = saa_crypto::secp256r1_verify(
-            &self.message_digest()?,
-            &self.signature,
-            self.pubkey.as_ref().unwrap()
-        )?;
-        ensure!(res, AuthError::generic("Passkey Signature verification failed"));
-        Ok(())
+        self.verify_with(&saa_crypto::NativeCrypto)
     }
 
 
     #[cfg(feature = "cosmwasm")]
-    fn verify_cosmwasm(
-        &self,  
-        #[allow(unused_variables)]    
-        api : &dyn saa_common::wasm::Api
-    ) -> Result<(), AuthError> {
-        #[cfg(feature = "no_api_r1")]
-        let res = saa_crypto::secp256r1_verify(
-            &self.message_digest()?,
-            &self.signature,
-            self.pubkey.as_ref().unwrap()
-        )?;
-        #[cfg(not(feature = "no_api_r1"))]
-        let res = api.secp256r1_verify(
-            &self.message_digest()?,
-            &self.signature,
-            &self.pubkey.as_ref().unwrap()
-        )?;
-        ensure!(res, AuthError::Signature("Passkey Signature verification failed".to_string()));
-        Ok(())
+    fn verify_cosmwasm(&self, api : &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 
 }
-
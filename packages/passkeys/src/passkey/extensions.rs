@@ -0,0 +1,160 @@
+use saa_common::{AuthError, Binary, String, ToString, Vec, ensure, format};
+use saa_crypto::cbor::{self, CborValue};
+use saa_schema::saa_type;
+
+
+/// Credential protection policy requested with `credProtect` extension
+#[saa_type]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum CredProtect {
+    /// Credential can be used with or without user verification
+    UserVerificationOptional,
+    /// Discovering the credential without its id requires user verification
+    UserVerificationOptionalWithCredentialIdList,
+    /// Every use of the credential requires user verification
+    UserVerificationRequired,
+}
+
+
+/// Output of `hmac-secret` extension
+#[saa_type]
+pub enum HmacSecretOutput {
+    /// Whether the credential supports the extension. Returned during registration
+    Enabled(bool),
+    /// Encrypted salted secrets returned during authentication
+    Output(Binary),
+}
+
+
+/// Device bound key returned by `devicePubKey` extension
+#[saa_type]
+pub struct DevicePublicKey {
+    /// Identifier of the authenticator model
+    pub aaguid      :   Binary,
+    /// CBOR encoded `COSE_Key` of the device bound key
+    pub dpk         :   Binary,
+    /// 0 when the key is scoped to the whole device and 1 when only to the relying party
+    pub scope       :   u8,
+    /// Nonce the device attestation was made with
+    pub nonce       :   Binary,
+    /// Format of the device attestation
+    pub fmt         :   String,
+    /// CBOR encoded attestation statement of the device key
+    pub att_stmt    :   Binary,
+}
+
+
+/// Decoded authenticator extension outputs
+#[saa_type]
+#[derive(Default)]
+pub struct ExtensionOutputs {
+    pub cred_protect    :   Option<CredProtect>,
+    pub hmac_secret     :   Option<HmacSecretOutput>,
+    /// Key for reading and writing the large blob of the credential
+    pub large_blob_key  :   Option<Binary>,
+    pub device_pub_key  :   Option<DevicePublicKey>,
+    /// Identifiers of other extensions that are present but not decoded
+    pub unknown         :   Vec<String>,
+}
+
+
+
+fn invalid(extension: &str) -> AuthError {
+    AuthError::generic(format!("Invalid output of {} extension", extension))
+}
+
+
+fn field<'a>(map: &'a CborValue, name: &str) -> Result<&'a CborValue, AuthError> {
+    map.get_text(name)
+        .ok_or_else(|| AuthError::MissingData(format!("Missing {} of devicePubKey output", name)))
+}
+
+
+impl CredProtect {
+    pub fn from_level(level: i128) -> Result<Self, AuthError> {
+        match level {
+            1 => Ok(Self::UserVerificationOptional),
+            2 => Ok(Self::UserVerificationOptionalWithCredentialIdList),
+            3 => Ok(Self::UserVerificationRequired),
+            _ => Err(invalid("credProtect")),
+        }
+    }
+}
+
+
+impl DevicePublicKey {
+    fn from_cbor(value: &CborValue) -> Result<Self, AuthError> {
+        // the output is a byte string with the encoded map but some authenticators put the map directly
+        let decoded;
+        let map = match value {
+            CborValue::Bytes(bytes) => {
+                decoded = cbor::from_slice(bytes)?;
+                &decoded
+            },
+            other => other,
+        };
+        ensure!(map.as_map().is_some(), invalid("devicePubKey"));
+        let bytes = |name: &str| field(map, name)?.as_bytes()
+            .map(|b| Binary::from(b.to_vec()))
+            .ok_or_else(|| invalid("devicePubKey"));
+
+        let aaguid = bytes("aaguid")?;
+        ensure!(aaguid.len() == 16, AuthError::InvalidLength("aaguid".to_string(), 16, aaguid.len() as u16));
+        let scope = field(map, "scope")?.as_int()
+            .filter(|s| *s == 0 || *s == 1)
+            .ok_or_else(|| invalid("devicePubKey"))? as u8;
+        let fmt = field(map, "fmt")?.as_text()
+            .ok_or_else(|| invalid("devicePubKey"))?
+            .to_string();
+        let att_stmt = field(map, "attStmt")?;
+        ensure!(att_stmt.as_map().is_some(), invalid("devicePubKey"));
+
+        Ok(Self {
+            aaguid,
+            dpk: bytes("dpk")?,
+            scope,
+            nonce: bytes("nonce")?,
+            fmt,
+            att_stmt: Binary::from(cbor::to_vec(att_stmt)),
+        })
+    }
+}
+
+
+impl ExtensionOutputs {
+
+    /// Decode the CBOR map of extension outputs found at the end of authenticator data
+    pub fn parse(data: &[u8]) -> Result<Self, AuthError> {
+        let value = cbor::from_slice(data)?;
+        let entries = value.as_map()
+            .ok_or_else(|| AuthError::generic("Extension outputs must be a map"))?;
+        let mut outputs = Self::default();
+
+        for (key, value) in entries {
+            let id = key.as_text()
+                .ok_or_else(|| AuthError::generic("Extension identifiers must be text"))?;
+            match id {
+                "credProtect" => {
+                    let level = value.as_int().ok_or_else(|| invalid(id))?;
+                    outputs.cred_protect = Some(CredProtect::from_level(level)?);
+                },
+                "hmac-secret" => {
+                    outputs.hmac_secret = Some(match value {
+                        CborValue::Bool(enabled) => HmacSecretOutput::Enabled(*enabled),
+                        CborValue::Bytes(output) => HmacSecretOutput::Output(Binary::from(output.clone())),
+                        _ => return Err(invalid(id)),
+                    });
+                },
+                "largeBlobKey" => {
+                    let key = value.as_bytes().ok_or_else(|| invalid(id))?;
+                    outputs.large_blob_key = Some(Binary::from(key.to_vec()));
+                },
+                "devicePubKey" => {
+                    outputs.device_pub_key = Some(DevicePublicKey::from_cbor(value)?);
+                },
+                _ => outputs.unknown.push(id.to_string()),
+            }
+        }
+        Ok(outputs)
+    }
+}
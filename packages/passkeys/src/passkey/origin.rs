@@ -0,0 +1,98 @@
+use saa_common::{AuthError, String, ToString, Vec, ensure, format};
+use saa_schema::saa_type;
+
+
+/// Prefix of the origins that Android apps use when calling the credential manager
+pub const ANDROID_ORIGIN_PREFIX : &str = "android:apk-key-hash:";
+
+
+
+/// Origins and relying party ids that a passkey may be used with
+#[saa_type]
+#[derive(Default)]
+pub struct OriginPolicy {
+    /// Allowed origins. A pattern like `https://*.example.com` matches any subdomain
+    /// of `example.com` but not the domain itself. Android apps are matched exactly
+    /// by their `android:apk-key-hash:<base64url hash>` origins
+    pub origins         :   Vec<String>,
+    /// Relying party ids that `rpIdHash` may be the hash of. Not checked when empty
+    pub rp_ids          :   Vec<String>,
+    /// Allow assertions requested from a cross origin iframe
+    pub cross_origin    :   bool,
+    /// Top level origins that may embed the iframe of a cross origin request.
+    /// Uses the same patterns as `origins`. Any top origin is accepted when empty
+    pub top_origins     :   Vec<String>,
+}
+
+
+
+/// Check whether an origin matches an allowed one or its wildcard pattern
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+    if pattern == origin {
+        return true;
+    }
+    let Some((scheme, domain)) = pattern.split_once("://*.") else {
+        return false;
+    };
+    let Some(host) = origin.strip_prefix(scheme).and_then(|o| o.strip_prefix("://")) else {
+        return false;
+    };
+    match host.strip_suffix(domain).and_then(|h| h.strip_suffix('.')) {
+        // only full subdomain labels are allowed in front of the domain
+        Some(sub) => !sub.is_empty() && sub.split('.').all(|label|
+            !label.is_empty() && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        ),
+        None => false,
+    }
+}
+
+
+
+impl OriginPolicy {
+
+    /// Policy that only allows the given origin
+    pub fn new(origin: impl ToString) -> Self {
+        Self { origins: saa_common::vec![origin.to_string()], ..Default::default() }
+    }
+
+    pub fn is_allowed_origin(&self, origin: &str) -> bool {
+        self.origins.iter().any(|p| origin_matches(p, origin))
+    }
+
+    pub fn is_allowed_top_origin(&self, origin: &str) -> bool {
+        self.top_origins.is_empty() || self.top_origins.iter().any(|p| origin_matches(p, origin))
+    }
+
+    pub fn is_android_origin(origin: &str) -> bool {
+        origin.starts_with(ANDROID_ORIGIN_PREFIX)
+    }
+
+
+    /// Check the origin related values of the client data and the `rpIdHash` of the authenticator data.
+    /// Presence of `top_origin` is treated as a cross origin request even if the flag isn't set
+    pub fn check(
+        &self,
+        origin: &str,
+        cross_origin: bool,
+        top_origin: Option<&str>,
+        rp_id_hash: &[u8],
+    ) -> Result<(), AuthError> {
+        ensure!(self.is_allowed_origin(origin), AuthError::OriginNotAllowed(origin.to_string()));
+        if cross_origin || top_origin.is_some() {
+            ensure!(
+                self.cross_origin && !Self::is_android_origin(origin),
+                AuthError::OriginNotAllowed(format!("{} in a cross origin context", origin))
+            );
+            if let Some(top) = top_origin {
+                ensure!(self.is_allowed_top_origin(top), AuthError::OriginNotAllowed(top.to_string()));
+            }
+        }
+        if !self.rp_ids.is_empty() {
+            ensure!(
+                self.rp_ids.iter().any(|id| saa_crypto::sha256(id.as_bytes()).as_slice() == rp_id_hash),
+                AuthError::generic("Relying party id hash doesn't match")
+            );
+        }
+        Ok(())
+    }
+}
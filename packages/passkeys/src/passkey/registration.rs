@@ -0,0 +1,237 @@
+use {
+    saa_common::{AuthError, CryptoApi, ToString, Vec, ensure},
+    saa_crypto::{cbor::{self, CborValue}, der, sha256},
+    super::{
+        cose::{PasskeyPublicKey, COSE_ALG_ES256},
+        authenticator::{AuthenticatorData, AUTH_DATA_MIN_LEN, FLAG_AT, FLAG_ED},
+        client_data::CollectedClientData,
+        extensions::ExtensionOutputs,
+        credential::PasskeyInfo,
+        origin::OriginPolicy,
+        utils::{base64_to_url, url_to_base64},
+    },
+};
+use saa_common::{Binary, String};
+use saa_schema::saa_type;
+
+
+/// Response of `navigator.credentials.create` needed to verify a registration ceremony
+#[saa_type]
+pub struct PasskeyRegistration {
+    /// Base64url encoded credential id
+    pub id                  :       String,
+    /// CBOR encoded attestation object
+    pub attestation_object  :       Binary,
+    /// Raw bytes of clientDataJSON exactly as returned by the browser
+    pub client_data_json    :       Binary,
+    /// Optional user handle that was passed as `user.id` to the authenticator
+    pub user_handle         :       Option<String>,
+}
+
+
+/// Values the relying party expects to find in the registration response
+#[saa_type]
+pub struct RegistrationOptions {
+    /// Challenge that was passed to `navigator.credentials.create`
+    pub challenge   :   Binary,
+    /// Origin of the client where the passkey is created
+    pub origin      :   String,
+    /// Relying party id that the authenticator scoped the credential to
+    pub rp_id       :   String,
+}
+
+
+
+/// Decoded `attStmt` of the supported attestation formats
+enum AttestationStatement {
+    None,
+    /// `packed` attestation with its algorithm, signature and an optional certificate chain.
+    /// Self attestation is used when the chain is empty
+    Packed { alg: i128, signature: Vec<u8>, x5c: Vec<Vec<u8>> },
+}
+
+
+/// Result of the checks that don't involve signature verification
+struct ParsedRegistration {
+    info: PasskeyInfo,
+    key: PasskeyPublicKey,
+    statement: AttestationStatement,
+    /// Message signed by the attestation key
+    signed_data: Vec<u8>,
+}
+
+
+
+fn parse_statement(fmt: &str, stmt: &CborValue) -> Result<AttestationStatement, AuthError> {
+    let entries = stmt.as_map()
+        .ok_or_else(|| AuthError::generic("Attestation statement must be a map"))?;
+    match fmt {
+        "none" => {
+            ensure!(entries.is_empty(), AuthError::generic("Attestation statement of `none` format must be empty"));
+            Ok(AttestationStatement::None)
+        },
+        "packed" => {
+            let alg = stmt.get_text("alg")
+                .and_then(|a| a.as_int())
+                .ok_or_else(|| AuthError::MissingData("Missing attestation algorithm".to_string()))?;
+            ensure!(stmt.get_text("ecdaaKeyId").is_none(), AuthError::generic("ECDAA attestation is not supported"));
+            let signature = stmt.get_text("sig")
+                .and_then(|s| s.as_bytes())
+                .ok_or_else(|| AuthError::MissingData("Missing attestation signature".to_string()))?
+                .to_vec();
+            let x5c = match stmt.get_text("x5c") {
+                Some(chain) => chain.as_array()
+                    .filter(|c| !c.is_empty())
+                    .ok_or_else(|| AuthError::generic("x5c must be a non empty array"))?
+                    .iter()
+                    .map(|c| c.as_bytes().map(|c| c.to_vec()))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| AuthError::generic("Certificates must be byte strings"))?,
+                None => Vec::new(),
+            };
+            ensure!(
+                x5c.is_empty() || alg == COSE_ALG_ES256,
+                AuthError::generic("Only ES256 attestation certificates are supported")
+            );
+            Ok(AttestationStatement::Packed { alg, signature, x5c })
+        },
+        _ => Err(AuthError::generic("Unsupported attestation format")),
+    }
+}
+
+
+
+impl PasskeyRegistration {
+
+    fn parse(&self, options: &RegistrationOptions) -> Result<ParsedRegistration, AuthError> {
+        let client_data = CollectedClientData::parse(&self.client_data_json)?;
+        ensure!(client_data.ty == "webauthn.create", AuthError::generic("Invalid client data type"));
+        ensure!(
+            client_data.challenge == base64_to_url(&options.challenge.to_base64()),
+            AuthError::PasskeyChallenge
+        );
+        ensure!(client_data.origin == options.origin, AuthError::generic("Unexpected origin"));
+
+        let object = cbor::from_slice(&self.attestation_object)?;
+        let fmt = object.get_text("fmt")
+            .and_then(|f| f.as_text())
+            .ok_or_else(|| AuthError::MissingData("Missing attestation format".to_string()))?;
+        let auth_data = object.get_text("authData")
+            .and_then(|a| a.as_bytes())
+            .ok_or_else(|| AuthError::MissingData("Missing authenticator data".to_string()))?;
+        let stmt = object.get_text("attStmt")
+            .ok_or_else(|| AuthError::MissingData("Missing attestation statement".to_string()))?;
+        let statement = parse_statement(fmt, stmt)?;
+
+        let parsed = AuthenticatorData::parse(auth_data)?;
+        ensure!(
+            parsed.rp_id_hash.as_slice() == sha256(options.rp_id.as_bytes()).as_slice(),
+            AuthError::generic("Relying party id hash doesn't match")
+        );
+        ensure!(parsed.user_present(), AuthError::generic("User presence flag is not set"));
+        let attested = parsed.attested_credential
+            .ok_or_else(|| AuthError::MissingData("Missing attested credential data".to_string()))?;
+
+        let id = Binary::from_base64(&url_to_base64(&self.id))
+            .map_err(|_| AuthError::generic("Credential id must be base64url encoded"))?;
+        ensure!(id.as_slice() == attested.credential_id.as_slice(), AuthError::generic("Credential id doesn't match"));
+        let key = PasskeyPublicKey::from_cose(&attested.public_key)?;
+        // P-256 keys are stored in SEC1 form for compatibility with the chain API
+        let pubkey = match &key {
+            PasskeyPublicKey::Es256(sec1) => sec1.clone(),
+            _ => attested.public_key,
+        };
+
+        // assertions carry the same authenticator data without the attested credential and extensions
+        let mut assertion_data = auth_data[..AUTH_DATA_MIN_LEN].to_vec();
+        assertion_data[32] &= !(FLAG_AT | FLAG_ED);
+
+        let signed_data = [auth_data, sha256(&self.client_data_json).as_slice()].concat();
+
+        Ok(ParsedRegistration {
+            info: PasskeyInfo {
+                authenticator_data: Binary::from(assertion_data),
+                // later assertions are bound to the relying party of the ceremony
+                origin_policy: Some(OriginPolicy {
+                    rp_ids: saa_common::vec![options.rp_id.clone()],
+                    ..OriginPolicy::new(&client_data.origin)
+                }),
+                origin: client_data.origin,
+                pubkey: Binary::from(pubkey),
+                cross_origin: client_data.cross_origin.unwrap_or(false),
+                user_handle: self.user_handle.clone(),
+                sign_count: Some(parsed.sign_count),
+            },
+            key,
+            statement,
+            signed_data,
+        })
+    }
+
+
+    /// Return the attestation key and the signature to verify if any
+    fn attestation_data(parsed: &ParsedRegistration) -> Result<Option<(PasskeyPublicKey, &[u8])>, AuthError> {
+        match &parsed.statement {
+            AttestationStatement::None => Ok(None),
+            AttestationStatement::Packed { alg, signature, x5c } => {
+                let key = match x5c.first() {
+                    Some(cert) => PasskeyPublicKey::Es256(der::certificate_public_key(cert)?.1.to_vec()),
+                    None => {
+                        ensure!(
+                            *alg == parsed.key.algorithm(),
+                            AuthError::generic("Self attestation must use the algorithm of the credential")
+                        );
+                        parsed.key.clone()
+                    },
+                };
+                Ok(Some((key, signature.as_slice())))
+            },
+        }
+    }
+}
+
+
+
+impl PasskeyRegistration {
+
+    /// Decoded extension outputs of the authenticator, e.g. to require a `credProtect` level.
+    /// Only meaningful after the registration is verified
+    pub fn extension_outputs(&self) -> Result<Option<ExtensionOutputs>, AuthError> {
+        let object = cbor::from_slice(&self.attestation_object)?;
+        let auth_data = object.get_text("authData")
+            .and_then(|a| a.as_bytes())
+            .ok_or_else(|| AuthError::MissingData("Missing authenticator data".to_string()))?;
+        AuthenticatorData::parse(auth_data)?.extension_outputs()
+    }
+
+
+    /// Verify the registration ceremony and return the info to be stored for the passkey.
+    /// Certificates of `packed` attestation are only used to verify the signature and
+    /// aren't checked against any trust anchors
+    pub fn verify_with(&self, api: &dyn CryptoApi, options: &RegistrationOptions) -> Result<PasskeyInfo, AuthError> {
+        let parsed = self.parse(options)?;
+        if let Some((key, signature)) = Self::attestation_data(&parsed)? {
+            let res = key.verify_with(api, &parsed.signed_data, signature)?;
+            ensure!(res, AuthError::Signature("Attestation signature verification failed".to_string()));
+        }
+        Ok(parsed.info)
+    }
+
+
+    /// Same as `verify_with` using the native crypto
+    #[cfg(feature = "native")]
+    pub fn verify(&self, options: &RegistrationOptions) -> Result<PasskeyInfo, AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto, options)
+    }
+
+
+    /// Same as `verify_with` using the crypto API of the chain
+    #[cfg(feature = "cosmwasm")]
+    pub fn verify_cosmwasm(
+        &self,
+        api : &dyn saa_common::wasm::Api,
+        options: &RegistrationOptions
+    ) -> Result<PasskeyInfo, AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api), options)
+    }
+}
@@ -1,4 +1,5 @@
-use saa_common::{AuthError,  Binary,  ToString, Verifiable, ensure};
+use saa_common::{AuthError,  Binary, CryptoApi, ToString, Verifiable, ensure};
+use saa_crypto::{der::HighSPolicy, hashes::HashAlgorithm};
 
 
 #[saa_schema::saa_type]
@@ -6,6 +7,28 @@ pub struct Secp256r1 {
     pub pubkey:    Binary,
     pub message:   Binary,
     pub signature: Binary,
+    /// Digest of the message that is signed. Defaults to `Sha256`
+    pub hash:      Option<HashAlgorithm>,
+    /// Treatment of high S signatures. Defaults to `Normalize`.
+    /// Chosen at registration and taken from the stored credential info by `build_credential`
+    pub high_s:    Option<HighSPolicy>,
+}
+
+
+
+impl Secp256r1 {
+    /// Signature in compact `r || s` form with the high S policy applied accepting DER encoded ones as well
+    fn compact_signature(&self) -> Result<[u8; 64], AuthError> {
+        saa_crypto::der::compact_signature_with(&self.signature, &saa_crypto::der::SECP256R1_ORDER, self.high_s())
+    }
+
+    pub fn hash(&self) -> HashAlgorithm {
+        self.hash.unwrap_or(HashAlgorithm::Sha256)
+    }
+
+    pub fn high_s(&self) -> HighSPolicy {
+        self.high_s.unwrap_or(HighSPolicy::Normalize)
+    }
 }
 
 
@@ -13,7 +36,7 @@ pub struct Secp256r1 {
 impl Verifiable for Secp256r1 {
 
     fn id(&self) -> saa_common::CredentialId {
-        self.pubkey.to_string()
+        saa_crypto::keys::sec1_credential_id(&self.pubkey)
     }
 
     fn validate(&self) -> Result<(), AuthError> {
@@ -22,42 +45,29 @@ impl Verifiable for Secp256r1 {
                 self.pubkey.len() > 0,
             AuthError::MissingData("Empty credential data".to_string())
         );
-        Ok(())
+        saa_crypto::keys::secp256r1_validate(&self.pubkey)?;
+        self.hash().validate(&self.message)
     }
 
-    #[cfg(feature = "native")]
-    fn verify(&self) -> Result<(), AuthError> {
-        let res = saa_crypto::secp256r1_verify(
-            &saa_crypto::hashes::sha256(&self.message), 
-            &self.signature, 
+    fn verify_with(&self, api: &dyn CryptoApi) -> Result<(), AuthError> {
+        let res = api.secp256r1_verify(
+            &self.hash().digest_with(api, &self.message)?, 
+            &self.compact_signature()?, 
             &self.pubkey
         )?;
         ensure!(res, AuthError::Signature("Signature verification failed".to_string()));
         Ok(())
     }
 
+    #[cfg(feature = "native")]
+    fn verify(&self) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::NativeCrypto)
+    }
+
 
     #[cfg(feature = "cosmwasm")]
-    fn verify_cosmwasm(
-        &self,
-        #[allow(unused_variables)]
-        api : &dyn saa_common::wasm::Api
-    ) -> Result<(), AuthError> {
-        use saa_crypto::hashes::sha256;
-        #[cfg(feature = "no_api_r1")]
-        let res = saa_crypto::secp256r1_verify(
-            &sha256(&self.message), 
-            &self.signature, 
-            &self.pubkey
-        )?;
-        #[cfg(not(feature = "no_api_r1"))]
-        let res = api.secp256r1_verify(
-            &sha256(&self.message), 
-            &self.signature, 
-            &self.pubkey
-        )?;
-        ensure!(res, AuthError::Signature("Signature verification failed".to_string()));
-        Ok(())
+    fn verify_cosmwasm(&self, api : &dyn saa_common::wasm::Api) -> Result<(), AuthError> {
+        self.verify_with(&saa_crypto::CosmwasmCrypto(api))
     }
 }
 
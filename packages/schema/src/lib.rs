@@ -33,7 +33,7 @@ pub use {borsh, saa_proto_solana::saa_type};
     not(feature = "cosmwasm"), 
     not(feature = "solana"))
 )]
-pub use {saa_proto_substrate::saa_type, scale};
+pub use saa_proto_substrate::saa_type;
 #[cfg(not(feature = "cosmwasm"))]
 pub use saa_proto_core::{saa_derivable, saa_str_struct};
 #[cfg(feature = "cosmwasm")]
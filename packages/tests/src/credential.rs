@@ -1,5 +1,13 @@
+mod cardano;
+mod curves;
 mod ethereum;
 mod passkey;
+mod registration;
+mod stellar;
+mod substrate;
+mod tezos;
+mod ton;
+mod tron;
 
 use std::str::FromStr;
 
@@ -0,0 +1,53 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{Binary, Verifiable};
+use smart_account_auth::CardanoSignData;
+use crate::utils::SIGN_MESSAGE_BASE64;
+
+
+const ADDRESS : &str = "610d05c5a2442286038aaf1205a4aaf41a2e9c3bb1c34962d98e63a504";
+const COSE_KEY : &str = "pAEBAycgBiFYIHm1Vi6P5lT5QHixEuipi6eQH4U65pW+1+DjkQutBJZk";
+const COSE_SIGN1 : &str = "hFgqogEnZ2FkZHJlc3NYHWENBcWiRCKGA4qvEgWkqvQaLpw7scNJYtmOY6UEoWZoYXNoZWT0WKx7ImNoYWluX2lkIjoiZWxnYWZhci0xIiwiY29udHJhY3RfYWRkcmVzcyI6InN0YXJzMXdnZXN6NWpyeDN1dnQyOWE5YXdrYWZ5NHAwNnJ1dHh2MnhkbnFwZXJkZTR0bXp4NG4yeXE5NW11bW4iLCJtZXNzYWdlcyI6WyJUZXN0aW5nIHNtYXJ0LWFjY291bnQtYXV0aCBsaWJyYXJ5Il0sIm5vbmNlIjoiMCJ9WECeP/f3T8ccsNMikmdGOIDop6sXzOQsNdRvfrNPD+eCv61H/5AV7/IZA3PSpzDXWWYiji9/KJ/8KHYokc9IzNcA";
+const COSE_SIGN1_HASHED : &str = "hFgqogEnZ2FkZHJlc3NYHWENBcWiRCKGA4qvEgWkqvQaLpw7scNJYtmOY6UEoWZoYXNoZWT1WBwe6WhLTy77jIBgl+rCjDIXRsd+zZ3xxSaU3bewWEAFK0X6fq5siedUf+N4042TxEVx2dcQYx/3H4BVnz+9IIGNv1uDnRXAxqv/DXADguDr56ohxfuj1+SZsYgSe10F";
+
+
+fn get_cardano(signature: &str) -> CardanoSignData {
+    CardanoSignData {
+        address: ADDRESS.to_string(),
+        message: Binary::from_base64(SIGN_MESSAGE_BASE64).unwrap(),
+        signature: Binary::from_base64(signature).unwrap(),
+        key: Binary::from_base64(COSE_KEY).unwrap(),
+    }
+}
+
+
+#[test]
+fn cardano_sign_data_verifiable() {
+    let deps = mock_dependencies();
+
+    for signature in [COSE_SIGN1, COSE_SIGN1_HASHED] {
+        let cred = get_cardano(signature);
+        assert!(cred.validate().is_ok());
+        assert!(cred.verify().is_ok());
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    }
+}
+
+
+#[test]
+fn cardano_sign_data_mismatches() {
+    let deps = mock_dependencies();
+
+    let mut cred = get_cardano(COSE_SIGN1);
+    cred.message = Binary::from(b"different message".to_vec());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err(), "Payload must match the message");
+
+    let mut cred = get_cardano(COSE_SIGN1_HASHED);
+    cred.address = format!("60{}", &ADDRESS[2..]);
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err(), "Address must match the protected headers");
+
+    let mut cred = get_cardano(COSE_SIGN1);
+    let mut key = cred.key.to_vec();
+    *key.last_mut().unwrap() ^= 1;
+    cred.key = key.into();
+    assert!(cred.verify().is_err(), "Key must match the address");
+}
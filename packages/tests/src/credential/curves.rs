@@ -0,0 +1,701 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{AuthError, Binary, Verifiable};
+use smart_account_auth::{
+    build_credential, find_credential_record, msgs::{AuthPayload, SignedDataMsg},
+    Credential, CredentialAddresses, CredentialData, CredentialInfo, CredentialName, CurveInfo, 
+    Bls12381, Ed25519, PasskeyCredential, Rsa, Secp256k1, Secp256k1Recoverable, Secp256r1,
+    types::{ClientData, Ed25519Mode, HashAlgorithm, HighSPolicy, RsaScheme}
+};
+
+
+const MESSAGE : &[u8] = b"der encoded signature";
+
+
+
+#[test]
+fn secp256r1_signature_encodings() {
+    let deps = mock_dependencies();
+    let cred = |signature: &str| Secp256r1 {
+        pubkey: Binary::from_base64("A1GnWAgziY6hsYPL1zUKQJkHjG7xweGOlwzXaDA18l59").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64(signature).unwrap(),
+        hash: None,
+        high_s: None,
+    };
+    for signature in [
+        "WMo/etkrDWRbBJgoRDrFaidIsYiFYYabcquI8XFFwWNuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==",
+        "MEQCIFjKP3rZKw1kWwSYKEQ6xWonSLGIhWGGm3KriPFxRcFjAiBuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==",
+        "MEUCIFjKP3rZKw1kWwSYKEQ6xWonSLGIhWGGm3KriPFxRcFjAiEAkXAQKnEFpRoCF3aZz2IIbcaBPAyCtl4GjV3WMLgaoYI=",
+    ] {
+        let cred = cred(signature);
+        assert!(cred.verify().is_ok());
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    }
+    // trailing byte after a valid DER signature
+    let cred = cred("MEQCIFjKP3rZKw1kWwSYKEQ6xWonSLGIhWGGm3KriPFxRcFjAiBuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzwA=");
+    assert!(cred.verify().is_err());
+}
+
+
+
+#[test]
+fn secp256k1_signature_encodings() {
+    let deps = mock_dependencies();
+    let cred = |signature: &str| Secp256k1 {
+        pubkey: Binary::from_base64("AjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7Gx").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64(signature).unwrap(),
+        hrp: None,
+        hash: None,
+        high_s: None,
+    };
+    for signature in [
+        "IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm8ZlF9hh2fkSk8TpSWDI/xX/FV4j1JaT4y61tZTiVcpSg==",
+        "MEQCICDCWB/78xK6RJ3I0X/pYX71f3aq0YUl9Pf/Mc8KCMZvAiAZlF9hh2fkSk8TpSWDI/xX/FV4j1JaT4y61tZTiVcpSg==",
+        "MEUCICDCWB/78xK6RJ3I0X/pYX71f3aq0YUl9Pf/Mc8KCMZvAiEA5mugnniYG7Ww7FrafNwDpr5ZZFdc7lCvBPuIOUbfF/c=",
+    ] {
+        let cred = cred(signature);
+        assert!(cred.verify().is_ok());
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    }
+    // signature of a different curve
+    let cred = cred("MEQCIFjKP3rZKw1kWwSYKEQ6xWonSLGIhWGGm3KriPFxRcFjAiBuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==");
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err());
+}
+
+
+
+#[test]
+fn high_s_policies() {
+    let deps = mock_dependencies();
+    let api = deps.as_ref().api;
+    // the same signatures as in the encoding tests with `s` replaced by `n - s`
+    let k1 = |high_s: Option<HighSPolicy>| Secp256k1 {
+        pubkey: Binary::from_base64("AjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7Gx").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64("IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm/ma6CeeJgbtbDsWtp83AOmvllkV1zuUK8E+4g5Rt8X9w==").unwrap(),
+        hrp: None,
+        hash: None,
+        high_s,
+    };
+    let r1_signature = Binary::from_base64("WMo/etkrDWRbBJgoRDrFaidIsYiFYYabcquI8XFFwWORcBAqcQWlGgIXdpnPYghtxoE8DIK2XgaNXdYwuBqhgg==").unwrap();
+    let r1 = |high_s: Option<HighSPolicy>| Secp256r1 {
+        pubkey: Binary::from_base64("A1GnWAgziY6hsYPL1zUKQJkHjG7xweGOlwzXaDA18l59").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: r1_signature.clone(),
+        hash: None,
+        high_s,
+    };
+    // the recovery id is flipped together with `s`
+    let recoverable = |high_s: Option<HighSPolicy>| Secp256k1Recoverable {
+        signer: "Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY".to_string(),
+        message: Binary::from(b"recover"),
+        signature: Binary::from_base64("AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5D40bEdP7YKCugD2S9w/wlz9F6tRUrDROeZZ2iGqUAKHQA=").unwrap(),
+        hash: None,
+        high_s,
+    };
+
+    for policy in [None, Some(HighSPolicy::Normalize), Some(HighSPolicy::Accept), Some(HighSPolicy::Reject)] {
+        let allowed = !matches!(policy, Some(HighSPolicy::Reject));
+        // native, `Api` and own secp256r1 implementation of `no_api_r1` give the same result
+        assert_eq!(k1(policy).verify().is_ok(), allowed);
+        assert_eq!(k1(policy).verify_cosmwasm(api).is_ok(), allowed);
+        assert_eq!(r1(policy).verify().is_ok(), allowed);
+        assert_eq!(r1(policy).verify_cosmwasm(api).is_ok(), allowed);
+        assert_eq!(recoverable(policy).verify().is_ok(), allowed);
+        assert_eq!(recoverable(policy).verify_cosmwasm(api).is_ok(), allowed);
+    }
+    
+    let digest = saa_crypto::sha256(MESSAGE);
+    let r1_key = r1(None).pubkey;
+    assert!(saa_crypto::secp256r1_verify(&digest, &r1_signature, &r1_key).unwrap());
+    assert!(api.secp256r1_verify(&digest, &r1_signature, &r1_key).unwrap());
+
+    // low S signatures are never affected
+    let low = Secp256k1 {
+        signature: Binary::from_base64("IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm8ZlF9hh2fkSk8TpSWDI/xX/FV4j1JaT4y61tZTiVcpSg==").unwrap(),
+        ..k1(Some(HighSPolicy::Reject))
+    };
+    assert!(low.verify().is_ok());
+    assert!(low.verify_cosmwasm(api).is_ok());
+
+    // stored with the rest of the non default curve settings
+    let info = Credential::Secp256k1(k1(Some(HighSPolicy::Reject))).info();
+    let stored : CurveInfo = saa_common::from_json(info.extension.clone().unwrap()).unwrap();
+    assert_eq!(stored, CurveInfo { high_s: Some(HighSPolicy::Reject), ..Default::default() });
+
+    // the policy is chosen when registering the credentials
+    for policy in [None, Some(HighSPolicy::Normalize), Some(HighSPolicy::Accept), Some(HighSPolicy::Reject)] {
+        let data = CredentialData {
+            credentials: vec![Credential::Secp256k1(k1(policy)), Credential::Secp256r1(r1(policy))],
+            use_native: None,
+            primary_index: None
+        };
+        assert!(data.validate().is_ok());
+        assert_eq!(data.verify_cosmwasm(api).is_ok(), policy != Some(HighSPolicy::Reject));
+    }
+
+    // and the stored one is applied when building the credential for an assertion
+    let record = (k1(None).pubkey.to_base64(), info);
+    let msg = SignedDataMsg { data: Binary::from(MESSAGE), signature: k1(None).signature, payload: None };
+    let cred = build_credential(record, msg, None).unwrap();
+    assert!(matches!(cred.verify_cosmwasm(api), Err(AuthError::Signature(_))));
+}
+
+
+#[test]
+fn crypto_backends() {
+    use smart_account_auth::crypto::{CosmwasmCrypto, NativeCrypto, RustCrypto};
+    use saa_common::CryptoApi;
+    let deps = mock_dependencies();
+    let backends : [&dyn CryptoApi; 3] = [&RustCrypto, &NativeCrypto, &CosmwasmCrypto(deps.as_ref().api)];
+
+    let k1 = |signature: &str| Secp256k1 {
+        pubkey: Binary::from_base64("AjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7Gx").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64(signature).unwrap(),
+        hrp: None,
+        hash: None,
+        high_s: None,
+    };
+    let r1 = Secp256r1 {
+        pubkey: Binary::from_base64("A1GnWAgziY6hsYPL1zUKQJkHjG7xweGOlwzXaDA18l59").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64("WMo/etkrDWRbBJgoRDrFaidIsYiFYYabcquI8XFFwWNuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==").unwrap(),
+        hash: None,
+        high_s: None,
+    };
+    let recoverable = Secp256k1Recoverable {
+        signer: "Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY".to_string(),
+        message: Binary::from(b"recover"),
+        signature: Binary::from_base64("AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5D40bEdP7YKCugD2S9w/wlz9F6tRUrDROeZZ2iGqUAKHQA=").unwrap(),
+        hash: None,
+        high_s: None,
+    };
+    let ed = |mode: Option<Ed25519Mode>| Ed25519 {
+        pubkey: Binary::from_base64("PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=").unwrap(),
+        message: Binary::from(b"\x72"),
+        signature: Binary::from_base64("kqAJqfDUyrhyDoILX2QlQKKye1QWUD+Ps3YiI+vbadoIWsHkPhWZbkWPNhPQ8R2MOHsurrQwKu6wDSkWErsMAA==").unwrap(),
+        mode,
+        hash: None,
+    };
+    let valid = k1("IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm8ZlF9hh2fkSk8TpSWDI/xX/FV4j1JaT4y61tZTiVcpSg==");
+    // r1 signature checked against the k1 key
+    let invalid = k1("WMo/etkrDWRbBJgoRDrFaidIsYiFYYabcquI8XFFwWNuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==");
+
+    for api in backends {
+        assert!(valid.verify_with(api).is_ok());
+        assert!(invalid.verify_with(api).is_err());
+        assert!(r1.verify_with(api).is_ok());
+        assert!(recoverable.verify_with(api).is_ok());
+        assert!(ed(Some(Ed25519Mode::Pure)).verify_with(api).is_ok());
+        assert!(ed(None).verify_with(api).is_err());
+        assert_eq!(api.sha256(MESSAGE).unwrap().as_slice(), saa_crypto::sha256(MESSAGE).as_slice());
+        
+        let data = CredentialData {
+            credentials: vec![Credential::Secp256k1(valid.clone()), Credential::Ed25519(ed(Some(Ed25519Mode::Pure)))],
+            use_native: None,
+            primary_index: None,
+        };
+        assert!(data.verify_with(api).is_ok());
+    }
+}
+
+
+
+#[test]
+fn ed25519_signing_modes() {
+    let deps = mock_dependencies();
+    let cred = |pubkey: &str, message: &[u8], signature: &str, mode: Option<Ed25519Mode>| Ed25519 {
+        pubkey: Binary::from_base64(pubkey).unwrap(),
+        message: Binary::from(message),
+        signature: Binary::from_base64(signature).unwrap(),
+        mode,
+        hash: None,
+    };
+    // RFC 8032 test 2
+    let pure = "kqAJqfDUyrhyDoILX2QlQKKye1QWUD+Ps3YiI+vbadoIWsHkPhWZbkWPNhPQ8R2MOHsurrQwKu6wDSkWErsMAA==";
+    let key = "PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=";
+    let ok = cred(key, b"\x72", pure, Some(Ed25519Mode::Pure));
+    assert!(ok.verify().is_ok());
+    assert!(ok.verify_cosmwasm(deps.as_ref().api).is_ok());
+    // same signature checked against the sha256 digest
+    assert!(cred(key, b"\x72", pure, None).verify().is_err());
+
+    // legacy mode signing the sha256 digest of the message
+    let digest = "soMXc3hfKrNBnx8E6MH1HDzrxFuGyV0KCfroi1/BPURT0bYRe4kUILQV/YvXXucLH/nbW3P8ximo6F8neBjCAA==";
+    assert!(cred(key, b"hello", digest, None).verify().is_ok());
+    assert!(cred(key, b"hello", digest, Some(Ed25519Mode::Digest)).verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(cred(key, b"hello", digest, Some(Ed25519Mode::Pure)).verify().is_err());
+
+    // RFC 8032 Ed25519ph test "abc"
+    let ph = cred(
+        "7Bcrk61eVjv0kyxw4SRQNMNUZ+8u/U1k6/gZaDRn4r8=",
+        b"abc",
+        "mKcCIvC4Ehqp0w+BPWg/gJ5GK0acf/h2OUmbuU5trkEx+FBCRjwqNVogA9BirfWqoQuMYeY2Biqq0RwqJgg0Bg==",
+        Some(Ed25519Mode::Prehash)
+    );
+    assert!(ph.verify().is_ok());
+    assert!(ph.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(Ed25519 { mode: Some(Ed25519Mode::Pure), ..ph }.verify().is_err());
+}
+
+
+
+#[test]
+fn ed25519_batch_verification() {
+    let deps = mock_dependencies();
+    let cred = |message: &[u8], signature: &str, mode: Option<Ed25519Mode>| Credential::Ed25519(Ed25519 {
+        pubkey: Binary::from_base64("PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=").unwrap(),
+        message: Binary::from(message),
+        signature: Binary::from_base64(signature).unwrap(),
+        mode,
+        hash: None,
+    });
+    let pure = "kqAJqfDUyrhyDoILX2QlQKKye1QWUD+Ps3YiI+vbadoIWsHkPhWZbkWPNhPQ8R2MOHsurrQwKu6wDSkWErsMAA==";
+    let digest = "soMXc3hfKrNBnx8E6MH1HDzrxFuGyV0KCfroi1/BPURT0bYRe4kUILQV/YvXXucLH/nbW3P8ximo6F8neBjCAA==";
+    let data = |credentials: Vec<Credential>| CredentialData {
+        credentials,
+        use_native: None,
+        primary_index: None,
+    };
+
+    let valid = data(vec![
+        cred(b"\x72", pure, Some(Ed25519Mode::Pure)),
+        cred(b"hello", digest, None),
+    ]);
+    assert!(valid.verify().is_ok());
+    assert!(valid.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    // the failing batch falls back to checking the credentials one by one
+    let invalid = data(vec![
+        cred(b"\x72", pure, Some(Ed25519Mode::Pure)),
+        cred(b"hello", digest, Some(Ed25519Mode::Pure)),
+    ]);
+    assert!(invalid.verify().is_err());
+    assert!(invalid.verify_cosmwasm(deps.as_ref().api).is_err());
+}
+
+
+#[test]
+fn curve_hash_algorithms() {
+    let deps = mock_dependencies();
+    let k1 = Secp256k1 {
+        pubkey: Binary::from_base64("Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY").unwrap(),
+        message: Binary::from(b"ledger"),
+        signature: Binary::from_base64("no9wTLwMuTF1W4yYTZUAfpaNs7s+kWyxyD4wRBqW2n973sA66dyG6PGQNyO+68oL1gmhJKm47fiJJBbFzbLthA==").unwrap(),
+        hrp: None,
+        hash: Some(HashAlgorithm::Keccak256),
+        high_s: None,
+    };
+    assert!(k1.verify().is_ok());
+    assert!(k1.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(Secp256k1 { hash: None, ..k1.clone() }.verify().is_err());
+
+    let r1 = Secp256r1 {
+        pubkey: Binary::from_base64("AgIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTt").unwrap(),
+        message: Binary::from_base64("6xprq3e712Y4si3L8bqJt6xXsuMdPg3Ylvh/gjyH1E8=").unwrap(),
+        signature: Binary::from_base64("wkVjBnfK3wUewQ0b6Yyt6lvrxvArszqvhGyrFXmXCHU10EXUQuU+4rQ91zF8lmKrO7EKX9NQHp/sAsNSOeMMIg==").unwrap(),
+        hash: Some(HashAlgorithm::Prehashed),
+        high_s: None,
+    };
+    assert!(r1.validate().is_ok());
+    assert!(r1.verify().is_ok());
+    assert!(r1.verify_cosmwasm(deps.as_ref().api).is_ok());
+    // prehashed messages must be exactly 32 bytes
+    let short = Secp256r1 { message: Binary::from(&r1.message[..31]), ..r1.clone() };
+    assert!(short.validate().is_err());
+    assert!(short.verify().is_err());
+
+    let ed = |hash: HashAlgorithm, signature: &str| Ed25519 {
+        pubkey: Binary::from_base64("PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=").unwrap(),
+        message: Binary::from(b"hsm"),
+        signature: Binary::from_base64(signature).unwrap(),
+        mode: None,
+        hash: Some(hash),
+    };
+    let sha512_256 = ed(
+        HashAlgorithm::Sha512_256,
+        "iySRmTuiqX9vqzzdfWjg7YPWm7nIOakMvazNqVtMGzXl+hMmKwdanJ2seidqXkC4xkTEmgmnHRbWIuk1DhtgDw=="
+    );
+    assert!(sha512_256.verify().is_ok());
+    let blake2b = ed(
+        HashAlgorithm::Blake2b256,
+        "5zLpUuvbRLRQ2l9kGIMrAyW6JRXxALOcwgJED4RnMsQEPQWuZpFQ/L0o4GmeNqbt4X/OxcRwqb45cVfFQRK+CQ=="
+    );
+    assert!(blake2b.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(Ed25519 { hash: Some(HashAlgorithm::Sha256), ..blake2b.clone() }.verify().is_err());
+    // digests can only be chosen in the default mode
+    assert!(Ed25519 { mode: Some(Ed25519Mode::Pure), ..blake2b.clone() }.validate().is_err());
+
+    // the scheme is kept in the extension of the stored info
+    let info = Credential::Ed25519(blake2b).info();
+    let stored : CurveInfo = saa_common::from_json(info.extension.unwrap()).unwrap();
+    assert_eq!(stored, CurveInfo { hash: Some(HashAlgorithm::Blake2b256), ..Default::default() });
+    assert!(Credential::Secp256k1(Secp256k1 { hash: None, ..k1 }).info().extension.is_none());
+}
+
+
+
+#[test]
+fn secp256k1_recoverable() {
+    let deps = mock_dependencies();
+    let address = "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c";
+    let signature = "AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5AHLk7iwEn19Rf8JtCPAPaKxlAvoWSFW1QmavYGJvY3JAE=";
+    let cred = |signer: &str, signature: &str| Secp256k1Recoverable {
+        signer: signer.to_string(),
+        message: Binary::from(b"recover"),
+        signature: Binary::from_base64(signature).unwrap(),
+        hash: None,
+        high_s: None,
+    };
+    for signer in ["Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY", address] {
+        let cred = cred(signer, signature);
+        assert!(cred.validate().is_ok());
+        assert!(cred.verify().is_ok());
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    }
+    // ethereum style recovery id
+    let eth = cred(address, "AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5AHLk7iwEn19Rf8JtCPAPaKxlAvoWSFW1QmavYGJvY3JBw=");
+    assert!(eth.verify_cosmwasm(deps.as_ref().api).is_ok());
+    // wrong recovery id recovers another key
+    let wrong = cred(address, "AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5AHLk7iwEn19Rf8JtCPAPaKxlAvoWSFW1QmavYGJvY3JAA=");
+    assert!(wrong.verify().is_err());
+    assert!(cred("cosmos1x36slx9at870e9rd53d2405n80s4ff9476ffvm", signature).verify_cosmwasm(deps.as_ref().api).is_err());
+    assert!(cred(address, "AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5AHLk7iwEn19Rf8JtCPAPaKxlAvoWSFW1QmavYGJvY3JA==").validate().is_err());
+    // short signatures are rejected without being validated first
+    for short in ["", "AQID", "AZCZl/kvj5/JFG7VzNqR2UHpX8XWBxa+daLU8TTAi5AHLk7iwEn19Rf8JtCPAPaKxlAvoWSFW1QmavYGJvY3JA=="] {
+        let short = cred(address, short);
+        assert_eq!(short.verify(), Err(AuthError::RecoveryParam));
+        assert_eq!(short.verify_cosmwasm(deps.as_ref().api), Err(AuthError::RecoveryParam));
+    }
+
+    // only the address is stored
+    let record = (address.to_string(), CredentialInfo {
+        name: CredentialName::Secp256k1Recoverable,
+        hrp: None,
+        extension: None,
+    });
+    let msg = SignedDataMsg {
+        data: Binary::from(b"recover"),
+        signature: Binary::from_base64(signature).unwrap(),
+        payload: None,
+    };
+    let built = build_credential(record, msg, None).unwrap();
+    assert!(built.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert_eq!(built.addresses("cosmos").unwrap(), CredentialAddresses {
+        cosmos: Some(address.to_string()),
+        evm: None,
+    });
+}
+
+
+
+#[test]
+fn bls12_381_aggregation() {
+    let deps = mock_dependencies();
+    let cred = |pubkey: &str, signature: &str| Bls12381 {
+        pubkey: Binary::from_base64(pubkey).unwrap(),
+        message: Binary::from([0xab; 32]),
+        signature: Binary::from_base64(signature).unwrap(),
+        dst: None,
+        proofs: None,
+    };
+    // keys of the ethereum consensus vectors signing 32 bytes of 0xab with the last one replaced
+    // by a key of a known secret so that all of them can sign the proofs of possession
+    let pubkeys = "pJHRsOzZu5F5ifDnTw3qBCLqxKhz5eJkTzaN/7mm4g/W4Qwbd2VNBnwGGPblp/easwGAP4taxKETNYH8Z23+3GDYkd1fqZAogF5epbCNNJGvddBwetqztwxqalgCF7+Bo73O2ZEbaZv/o71XLxkhiXkfKfNtmDCHs2xyR+ed5ARqQZ6WnVpkF79zKrGCP1pl";
+    let proofs = Some(Binary::from_base64("uAPrDtk+oQIkpztrnHJXlr6fX+/SFe96W5cjTMlWz2hw22Ent+TYJOxiJ2B454fbBVhM4a2/B2vAgIyg8Vtz1ZBgJUslOT2V38er482lZoQqrt9Qu7BiquG7tu87H3fhiLsxsn6uIwOOFPnZ0bYoo59YgbUnjDxvAkn4G6DesfaKpfiEeFTWVUBRqoEP3xzbAt9K96Vkexqkr7YOxtRG7hevJKilCHb/2vm/R1A47F+OvtocHGoyICk+I7E6ml0mjfg+9Z5Ml491TrQjD3xPsdRwfHM019QCr8DL2BZD44PJQ03GJhPIgdD+C45ZxBQZEYj2chjWIghZj3N1osjeSZZ1m2aCfPEh+39lzUAUZ3dn2SbbhthE5dIB6O5nOeqV").unwrap());
+    let aggregated = "mCknzwUW8weIfuVbMewbEL+oJ3CsorTYzJGbS056K8RK3DzsqpstJRN5iewdD41AFI/XqiaxLL3sntZs2ICwZsAAhW+GNGXhQmxHcOfihXvbA/8gI/3P2Pn7xXSA4sWp";
+    let signatures = "kTR7zPdA2FkDj83K8jPuzrKkNryq7psqo7+3Dv4p37JndWLMvqHI4GH7mXGwdTwkBiL6t4SJzpZ2gln8ATYDRtpbn1eeXaDZQeTGuhig5kkGCCN1OU8zf6GvK3EnsNEhlnTiIoA0Un9MCDIGAysCAxD6zhVtSkaF4vyuwvbzZlqmNdkDR7bOEk64eSZrHoAdGF3jagoom4XpA5ZiY08u6h4C5nC8erhJ0AanCy+TuEWXVYoFuHnI1EXzh6XVtlPfpwugiKofvo0hcpNyOuB34UUkIU1dGAmpH0qwLbDWg8r5okOr5hHHWGdDJeyh7EQ1DsZkzO5GzxuwPnBPZuPIYM2mku7NScb2K0YA7cE10kcnS30qr9wp7jNE2AXNjDXp";
+    let proven = |pubkey: &str, signature: &str| Bls12381 { proofs: proofs.clone(), ..cred(pubkey, signature) };
+    let single = cred(
+        "tT0hpM/VYsRpzIFRTUzlprV32EA9MqOU3CZd0ZC0f6n4Kf3Xljr9+XLl53hUBR9v", 
+        "roJ0fd7v5P1kz5ztubBK4+ikNCDNJV48fNBqjYi3x/hjhUNxmYHF0W+jUnxGjCXwAmcEppUb3okTYMfo0S3e4FWQBMzb5gRrVbrhslful/fNuVV3PXzymt88y7mXXk65"
+    );
+
+    for cred in [single, proven(pubkeys, aggregated), proven(pubkeys, signatures)] {
+        assert!(cred.validate().is_ok());
+        assert!(cred.verify().is_ok());
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    }
+    assert_eq!(cred(pubkeys, aggregated).signers(), 3);
+
+    // aggregation of keys without proofs of possession
+    let unproven = cred(pubkeys, aggregated);
+    assert!(unproven.validate().is_err());
+    assert!(matches!(unproven.verify(), Err(AuthError::MissingData(_))));
+    assert!(unproven.verify_cosmwasm(deps.as_ref().api).is_err());
+    // proofs in a different order
+    let proofs = proofs.clone().unwrap();
+    let swapped = Bls12381 { 
+        proofs: Some(Binary::from([&proofs[96..192], &proofs[..96], &proofs[192..]].concat())), 
+        ..cred(pubkeys, aggregated) 
+    };
+    assert!(matches!(swapped.verify(), Err(AuthError::Signature(_))));
+
+    // rogue key cancelling out the first one lets the attacker sign alone but can't be proven
+    let rogue = Bls12381 {
+        proofs: Some(Binary::from_base64("uAPrDtk+oQIkpztrnHJXlr6fX+/SFe96W5cjTMlWz2hw22Ent+TYJOxiJ2B454fbBVhM4a2/B2vAgIyg8Vtz1ZBgJUslOT2V38er482lZoQqrt9Qu7BiquG7tu87H3fhha5duG4FnATecJMvPy/8nrDRPhMtN/m25dJ17wFBZl7KbE0pLi7xis/76jTxRCd4DMPlecRmgsZ5/BcrhcAt1vcJVOnLAh+ybeShtymYrED+58g11RIqDRvYl/ZdsYSL").unwrap()),
+        ..cred(
+            "pJHRsOzZu5F5ifDnTw3qBCLqxKhz5eJkTzaN/7mm4g/W4Qwbd2VNBnwGGPblp/eamEskhGnQSNY7svE2jWK4y+BTyLCQyCARgSukLPVuYxbw1JhFp8eATu+CMYSun7Oa",
+            "jRv7PKck1FlJmadEwxBMjvqKu/8yJ2Kp6PBNhc09SEcoGpp/Z54I/a/E0RhDfEEuGZDDmTI8OM8JOhejRca41aloqDd1FMuM/I6a2CUVa9I3R8T0VExbkQvm7PzucRss"
+        )
+    };
+    let rogue_unchecked = saa_crypto::bls12_381::bls12_381_verify(
+        &rogue.message, &rogue.signature, &rogue.pubkey, rogue.dst().as_bytes()
+    );
+    assert!(rogue_unchecked.unwrap());
+    assert!(rogue.validate().is_ok());
+    assert!(matches!(rogue.verify(), Err(AuthError::Signature(_))));
+    assert!(rogue.verify_cosmwasm(deps.as_ref().api).is_err());
+
+    // one of the signers is missing
+    let missing = Bls12381 { proofs: Some(Binary::from(&proofs[..192])), ..cred(&pubkeys[..128], aggregated) };
+    assert!(missing.verify().is_err());
+    assert!(missing.verify_cosmwasm(deps.as_ref().api).is_err());
+    assert!(cred(&pubkeys[..128], signatures).validate().is_err());
+    // other domain separation tag
+    let dst = Bls12381 { dst: Some("BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_".to_string()), ..proven(pubkeys, aggregated) };
+    assert!(dst.verify_cosmwasm(deps.as_ref().api).is_err());
+
+    // proofs are stored with the keys and used when building the credential
+    let info = Credential::Bls12381(proven(pubkeys, aggregated)).info();
+    let msg = SignedDataMsg { data: Binary::from([0xab; 32]), signature: Binary::from_base64(aggregated).unwrap(), payload: None };
+    let built = build_credential((pubkeys.to_string(), info), msg, None).unwrap();
+    assert!(built.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    // verified as a part of credential data next to other credentials
+    let data = |credentials: Vec<Credential>| CredentialData {
+        credentials,
+        use_native: None,
+        primary_index: None,
+    };
+    let valid = data(vec![
+        Credential::Bls12381(proven(pubkeys, aggregated)),
+        Credential::Bls12381(proven(pubkeys, signatures)),
+    ]);
+    assert!(valid.verify().is_ok());
+    assert!(valid.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(valid.verify_with(&smart_account_auth::crypto::RustCrypto).is_ok());
+    let invalid = data(vec![
+        Credential::Bls12381(proven(pubkeys, aggregated)),
+        Credential::Bls12381(missing),
+    ]);
+    assert!(invalid.verify().is_err());
+    assert!(invalid.verify_cosmwasm(deps.as_ref().api).is_err());
+}
+
+
+
+#[test]
+fn rsa_signature_schemes() {
+    let deps = mock_dependencies();
+    let pkcs1 = "MIIBCgKCAQEAshA/Ids7GRzuejg1M8eiE6iVKvlN3HDfVA8eha1/v8GALtIp6Q5LuAzw/bW5hGdTMmKjcNz26+DHBNf1dPg3Mx2PYLGzS+vQCQNy9YjMXH3stJoc/R4LLbEah/hIxshZpWn4k4hsi+/FUicuTqzfB7T2GcOuPUOj/AVaANGboFFJWhnfMRdGAfUxiASejqCZInzW9ncMLrFStTGJf8uWdFV2AECPOdxf8qwGWtMWjJcnSQVA8Y+iHCyfkm1DuXxgNFVh6KA4AWsf/P5TYYnaupwX1HkRxlbnDgbrdglWdVxejblwzJloC6hs765eSaFMWqGab3pyo3xx6h08u4QclwIDAQAB";
+    let spki = "MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAshA/Ids7GRzuejg1M8eiE6iVKvlN3HDfVA8eha1/v8GALtIp6Q5LuAzw/bW5hGdTMmKjcNz26+DHBNf1dPg3Mx2PYLGzS+vQCQNy9YjMXH3stJoc/R4LLbEah/hIxshZpWn4k4hsi+/FUicuTqzfB7T2GcOuPUOj/AVaANGboFFJWhnfMRdGAfUxiASejqCZInzW9ncMLrFStTGJf8uWdFV2AECPOdxf8qwGWtMWjJcnSQVA8Y+iHCyfkm1DuXxgNFVh6KA4AWsf/P5TYYnaupwX1HkRxlbnDgbrdglWdVxejblwzJloC6hs765eSaFMWqGab3pyo3xx6h08u4QclwIDAQAB";
+    let cred = |pubkey: &str, signature: &str, scheme: Option<RsaScheme>| Rsa {
+        pubkey: Binary::from_base64(pubkey).unwrap(),
+        message: Binary::from(b"eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiIxMjMifQ"),
+        signature: Binary::from_base64(signature).unwrap(),
+        scheme,
+    };
+    let rs256 = "XND0zqwGpLOS+RPw7xqqoXh12XBH8a3/dJOiA7XG0bkwlAwkNyiJtjue9AanOyRxZTYa2EtSs+t8Mb+YNx/A0Xu1lsr73mjnH696WfhAlYhzF+wNis2NqryFeGNmwtxo1wtKG84ct7MYZUVGqA31dQziLFm52VQ4xXYKfeA+PjJ6M63UlbNkaU/jBw1mOIpFibUpWdsvJtgIQvPZTm7NJLmFLvz3iy+6haItvOrZw+0YFeKwVBxa08ZQUaJ/7o4nkMHtwOv6NAoI7kEvH37r5YRrEs2TPoX8Cjj3yjKpQE9XeJ9d6vgQrHvu2p5EznZ08/GUngi0V7TNmMck0bS+Fw==";
+    let rs384 = "EA8ggo7CpYgINOvundY6gGPSBK7uJ4m7WsBE7ZuQAmIwbeSFM+10FOpQHXabZWzwztPsnHOZ8vJsdlr7hgi98GKqOXrWToeNj1KfjZiZgB5ioeUkT0mjLzYuQy67GtGesppTKwXWJUgU3j3B23VDfPwfDri0GnaWS0C1NJTzK9i6ZSa26n0GXDN0L1rq6r1IicnPQiW0XJtYiSUF5Cp1jhmgjRDe9l4YUHdaj1O3Svo8ceefaeNxRxLI19yvnXt6tVfGfCYk68+ysFT9DkOqyGIoByA5S/aC0N8+wJB3bIfwYP+v5PWkXwAuC8v5FNp0648gJasAJgTmzmGhhWn+yA==";
+    let ps512 = "Pldnz6VhermERcQPCCgagmhMsgxU9nmUQEfI1gSHf4kFIvO/2cUvf5iyz3BOhrJAxD4weks9r7tqnh+mNGoOZIKkWuia7blOQi58C7337NKU+IjTRUVZxI4OowXF1XLviMTwhJrAtVhA9BY4kOB7NzIvrfgG8IP9IHhKk+MWY7FQNkuIlE8LIClDFIc8u9+kK4PKcK2PRqTEtj7Uh00SNAGAN8hEUI3qedwjqfvl8rvI7aIkJ7UvVOul/MZG97Y6EtbX84YXBeRmpTattXu1MlEuew3fQy6vbICh5GVAK0VdgNkyYMoLgKwX6Pt8vkHfNpPhZoBFRtDTrVUrWJvQCA==";
+
+    // both key encodings
+    for pubkey in [pkcs1, spki] {
+        for cred in [
+            cred(pubkey, rs256, None),
+            cred(pubkey, rs384, Some(RsaScheme::Rs384)),
+            cred(pubkey, ps512, Some(RsaScheme::Ps512)),
+        ] {
+            assert!(cred.validate().is_ok());
+            assert!(cred.verify().is_ok());
+            assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+        }
+    }
+    assert!(cred(pkcs1, rs384, None).verify().is_err());
+    assert!(cred(pkcs1, ps512, Some(RsaScheme::Rs512)).verify().is_err());
+    // and the same id for both
+    assert_eq!(cred(pkcs1, rs256, None).id(), spki);
+    assert_eq!(cred(spki, rs256, None).id(), spki);
+    // keys shorter than 2048 bits are rejected
+    let small = "MIGJAoGBAN+e+nXewa+dGwH/D6mJ9akLoKGRijwX4dpmyYVxp/5cyiPkYrMMmYVKL7GX0SvfLJEawsnKyGRLLhn8x/kL/m1BBDov8dcccZLrA0qfmZsAVTt9KpS2T/U3ypOW/aNq8cP559vHaWLrH1enhtbH+z8xhms61R5VLo2MbmGw4VlzAgMBAAE=";
+    assert!(cred(small, rs256, None).validate().is_err());
+
+    // the scheme is restored from the stored info
+    let stored = Credential::Rsa(cred(spki, ps512, Some(RsaScheme::Ps512))).info();
+    let msg = SignedDataMsg {
+        data: Binary::from(b"eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiIxMjMifQ"),
+        signature: Binary::from_base64(ps512).unwrap(),
+        payload: None,
+    };
+    let built = build_credential((spki.to_string(), stored), msg, None).unwrap();
+    assert!(built.verify_cosmwasm(deps.as_ref().api).is_ok());
+}
+
+
+
+#[test]
+fn credential_addresses() {
+    let k1 = |pubkey: &str| Credential::Secp256k1(Secp256k1 {
+        pubkey: Binary::from_base64(pubkey).unwrap(),
+        message: Binary::default(),
+        signature: Binary::default(),
+        hrp: None,
+        hash: None,
+        high_s: None,
+    });
+    let expected = CredentialAddresses {
+        cosmos: Some("cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c".to_string()),
+        evm: Some("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".to_string()),
+    };
+    // compressed and uncompressed forms of the same key
+    assert_eq!(k1("Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY").addresses("cosmos").unwrap(), expected);
+    assert_eq!(
+        k1("BHm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeYSDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj/sQ1Lg=").addresses("cosmos").unwrap(), 
+        expected
+    );
+    assert!(k1("AA==").addresses("cosmos").is_err());
+
+    // `address.Hash("cosmos.crypto.secp256r1.PubKey", key)` of the Cosmos SDK computed independently
+    let r1_address = CredentialAddresses {
+        cosmos: Some("cosmos1tp7jxgezguhnt3ry3s6cj3kxqxsnjmgmtzt7dm2058zdxy6lljnspmapxe".to_string()),
+        evm: None,
+    };
+    let r1 = Credential::Secp256r1(Secp256r1 {
+        pubkey: Binary::from_base64("AgIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTt").unwrap(),
+        message: Binary::default(),
+        signature: Binary::default(),
+        hash: None,
+        high_s: None,
+    });
+    // passkey holding the same key in uncompressed form
+    let passkey = Credential::Passkey(PasskeyCredential {
+        id: String::default(),
+        pubkey: Some(Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap()),
+        signature: Binary::default(),
+        authenticator_data: Binary::default(),
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None),
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    });
+    assert_eq!(passkey.addresses("cosmos").unwrap(), r1_address);
+    assert_eq!(r1.addresses("cosmos").unwrap(), r1_address);
+
+    // the same scheme is used for the address of the credential and of its stored info
+    let deps = mock_dependencies();
+    let api = deps.as_ref().api;
+    let humanized = r1.addresses("cosmwasm").unwrap().cosmos.unwrap();
+    assert_eq!(r1.cosmos_address(api).unwrap().as_str(), humanized);
+    assert_eq!(passkey.cosmos_address(api).unwrap().as_str(), humanized);
+    assert_eq!(r1.info().cosmos_address(api, r1.id()).unwrap().as_str(), humanized);
+    let k1_hrp = Credential::Secp256k1(Secp256k1 { hrp: Some("cosmos".to_string()), ..match k1("Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY") {
+        Credential::Secp256k1(c) => c,
+        _ => unreachable!()
+    }});
+    assert_eq!(Some(k1_hrp.cosmos_address(api).unwrap().to_string()), expected.cosmos);
+
+    // accounts stored under the previous derivation can still be looked up
+    let legacy = api.addr_humanize(&smart_account_auth::crypto::pubkey_to_canonical(r1.id().as_bytes())).unwrap();
+    assert_eq!(r1.legacy_cosmos_address(api).unwrap(), legacy);
+    assert_eq!(r1.info().legacy_cosmos_address(api, r1.id()).unwrap(), legacy);
+    assert_ne!(r1.cosmos_address(api).unwrap(), legacy);
+
+    let ed = Credential::Ed25519(Ed25519 {
+        pubkey: Binary::from_base64("iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w=").unwrap(),
+        message: Binary::default(),
+        signature: Binary::default(),
+        mode: None,
+        hash: None,
+    });
+    assert_eq!(ed.addresses("cosmos").unwrap(), CredentialAddresses {
+        cosmos: Some("cosmos1x36slx9at870e9rd53d2405n80s4ff9476ffvm".to_string()),
+        evm: None,
+    });
+}
+
+
+
+#[test]
+fn pubkey_canonicalisation() {
+    use smart_account_auth::crypto::keys;
+    let compressed = Binary::from_base64("Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY").unwrap();
+    let uncompressed = Binary::from_base64("BHm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeYSDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj/sQ1Lg=").unwrap();
+    assert_eq!(keys::compress_sec1(&uncompressed).unwrap().as_slice(), compressed.as_slice());
+    assert_eq!(keys::secp256k1_decompress(&compressed).unwrap().as_slice(), uncompressed.as_slice());
+    assert!(keys::secp256k1_validate(&uncompressed).is_ok());
+    // x coordinate that isn't on the curve
+    assert!(keys::secp256k1_validate(&[&[0x02], [0xff; 32].as_slice()].concat()).is_err());
+
+    let k1 = |pubkey: &Binary| Secp256k1 {
+        pubkey: pubkey.clone(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from([1u8; 64].as_slice()),
+        hrp: None,
+        hash: None,
+        high_s: None,
+    };
+    // both encodings identify the same credential
+    assert_eq!(k1(&uncompressed).id(), compressed.to_string());
+    assert_eq!(k1(&compressed).id(), compressed.to_string());
+    assert!(k1(&uncompressed).validate().is_ok());
+    assert!(k1(&Binary::from(&uncompressed[..64])).validate().is_err());
+    assert!(k1(&Binary::from([&[0x05], &compressed[1..]].concat())).validate().is_err());
+
+    let r1 = |pubkey: &str| Secp256r1 {
+        pubkey: Binary::from_base64(pubkey).unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from([1u8; 64].as_slice()),
+        hash: None,
+        high_s: None,
+    };
+    assert_eq!(
+        r1("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").id(),
+        r1("AgIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTt").id()
+    );
+    // well formed encodings of points that aren't on the curves
+    let off_curve = Binary::from([&[0x02], [0xff; 32].as_slice()].concat());
+    assert!(k1(&off_curve).validate().is_err());
+    assert!(r1(&off_curve.to_base64()).validate().is_err());
+    let mut r1_uncompressed = Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap().to_vec();
+    r1_uncompressed[64] ^= 1;
+    assert!(r1(&Binary::from(r1_uncompressed).to_base64()).validate().is_err());
+
+    let recoverable = Secp256k1Recoverable {
+        signer: uncompressed.to_string(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from([1u8; 65].as_slice()),
+        hash: None,
+        high_s: None,
+    };
+    assert_eq!(recoverable.id(), compressed.to_string());
+
+    let ed = |pubkey: &[u8]| Ed25519 {
+        pubkey: Binary::from(pubkey),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from([1u8; 64].as_slice()),
+        mode: None,
+        hash: None,
+    };
+    assert!(ed(&Binary::from_base64("iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w=").unwrap()).validate().is_ok());
+    assert!(ed(&[1u8; 31]).validate().is_err());
+    // y = 2 has no matching point on the curve
+    let mut y = [0u8; 32];
+    y[0] = 2;
+    assert!(ed(&y).validate().is_err());
+
+    // records stored under uncompressed ids are still found by the compressed ones and vice versa
+    let record = |id: &Binary| (id.to_string(), CredentialInfo { name: CredentialName::Secp256k1, hrp: None, extension: None });
+    let payload = |id: &Binary| AuthPayload { hrp: None, credential_id: Some(id.to_string()), extension: None };
+    assert_eq!(keys::canonical_credential_id(&uncompressed.to_string()), k1(&uncompressed).id());
+    assert_eq!(keys::canonical_credential_id("cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"), "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c");
+    let found = find_credential_record(vec![record(&uncompressed)], Some(&payload(&compressed))).unwrap();
+    assert_eq!(found.0, uncompressed.to_string());
+    let found = find_credential_record(vec![record(&compressed)], Some(&payload(&uncompressed))).unwrap();
+    assert_eq!(found.0, compressed.to_string());
+    // only records of secp256 keys are matched by their canonical ids
+    let other = (uncompressed.to_string(), CredentialInfo { name: CredentialName::Ed25519, hrp: None, extension: None });
+    assert!(find_credential_record(vec![other], Some(&payload(&compressed))).is_err());
+}
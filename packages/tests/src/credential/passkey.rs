@@ -2,7 +2,10 @@ use crate::types::{Coin, CosmosMsg, StakingMsg};
 use cosmwasm_std::testing::mock_dependencies;
 use saa_common::{to_json_binary, Binary, Verifiable};
 use smart_account_auth::{utils::passkey::base64_to_url,
-    types::{ClientDataOtherKeys, ClientData},
+    types::{
+        ClientDataOtherKeys, ClientDataValue, ClientData, AuthenticatorOptions, PasskeyPayload,
+        CredProtect, HmacSecretOutput, PasskeyPublicKey
+    },
     msgs::MsgDataToSign, PasskeyCredential,
 };
 
@@ -43,7 +46,9 @@ fn can_check_passkeys_simple() {
             false,
             None
         ), 
-        user_handle: None
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
     };
 
     let res = credential.verify_cosmwasm(deps.api);
@@ -85,7 +90,9 @@ fn can_check_passkeys_data_string() {
             false,
             None
         ), 
-        user_handle: None
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
     };
     let res = credential.verify_cosmwasm(deps.api);
     assert!(res.is_ok());
@@ -136,7 +143,9 @@ fn can_check_passkeys_data_actions() {
             false,
             None
         ), 
-        user_handle: None
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
     };
     let res = credential.verify_cosmwasm(deps.api);
     assert!(res.is_ok());
@@ -177,7 +186,9 @@ fn pass_verification_with_other_keys() {
                 ClientDataOtherKeys::new(Some(OTHER_KEY.to_string()))
             )
         ), 
-        user_handle: None
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
     };
     let res = credential.verify_cosmwasm(deps.api);
     assert!(res.is_ok());
@@ -194,9 +205,290 @@ fn pass_verification_with_other_keys() {
             false,
             None
         ), 
-        user_handle: None
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
     };
     let res = credential.verify_cosmwasm(deps.api);
     assert!(res.is_err());
 
-}
\ No newline at end of file
+}
+
+
+#[test]
+fn passkey_authenticator_data_options() {
+    let auth_data = Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap();
+    let credential = |auth_data: Vec<u8>| PasskeyCredential { 
+        id: String::default(),
+        pubkey: None, 
+        signature: Binary::default(), 
+        authenticator_data: Binary::from(auth_data), 
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None), 
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    };
+
+    let cred = credential(auth_data.to_vec());
+    let data = cred.parsed_authenticator_data().unwrap();
+    assert!(data.user_present() && data.user_verified() && data.backup_eligible() && data.backed_up());
+    assert_eq!(data.sign_count, 0);
+
+    let opts = AuthenticatorOptions {
+        rp_id: Some("localhost".to_string()),
+        user_presence: true,
+        user_verification: true,
+        sign_count: Some(0),
+    };
+    assert!(cred.check_authenticator_data(&opts).is_ok());
+    assert!(cred.check_authenticator_data(&AuthenticatorOptions {
+        rp_id: Some("example.com".to_string()),
+        ..opts.clone()
+    }).is_err());
+
+    // only user presence
+    let mut presence_only = auth_data.to_vec();
+    presence_only[32] = 0x01;
+    let cred = credential(presence_only.clone());
+    assert!(cred.check_authenticator_data(&AuthenticatorOptions { user_verification: false, ..opts.clone() }).is_ok());
+    assert!(cred.check_authenticator_data(&opts).is_err());
+
+    // backup state without eligibility
+    presence_only[32] = 0x11;
+    assert!(credential(presence_only).parsed_authenticator_data().is_err());
+
+    // signature counters
+    let mut counted = auth_data.to_vec();
+    counted[33..37].copy_from_slice(&5u32.to_be_bytes());
+    let cred = credential(counted);
+    assert_eq!(cred.parsed_authenticator_data().unwrap().sign_count, 5);
+    assert!(cred.check_authenticator_data(&AuthenticatorOptions { sign_count: Some(4), ..opts.clone() }).is_ok());
+    assert!(cred.check_authenticator_data(&AuthenticatorOptions { sign_count: Some(5), ..opts.clone() }).is_err());
+    let zero = credential(auth_data.to_vec());
+    assert!(zero.check_authenticator_data(&AuthenticatorOptions { sign_count: Some(5), ..opts.clone() }).is_err());
+
+    // truncated and trailing data
+    assert!(credential(auth_data[..36].to_vec()).validate().is_err());
+    assert!(credential([auth_data.as_slice(), &[0]].concat()).validate().is_err());
+}
+
+
+
+#[test]
+fn passkey_raw_client_data_json() {
+    let deps = mock_dependencies();
+    let deps = deps.as_ref();
+    let pubkey = Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap();
+    let authenticator_data = Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap();
+
+    // cross origin request with `topOrigin` and `androidPackageName` that `ClientData` doesn't know about
+    let client_data_json = Binary::from_base64("eyJ0eXBlIjoid2ViYXV0aG4uZ2V0IiwiY2hhbGxlbmdlIjoiTVRJeiIsIm9yaWdpbiI6Imh0dHA6Ly9sb2NhbGhvc3Q6NTE3MyIsImNyb3NzT3JpZ2luIjp0cnVlLCJ0b3BPcmlnaW4iOiJodHRwczovL3RvcC5leGFtcGxlIiwiYW5kcm9pZFBhY2thZ2VOYW1lIjoiY29tLmV4YW1wbGUuYXBwIn0=").unwrap();
+    let mut credential = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(pubkey.clone()), 
+        signature: Binary::from_base64("DB0r5uvsbAeXlpCMwm4Nf/x1G27NgKi9Is83BnSGlHRZaztYt+UE+FL2LYFSRwDgUoWAhHz3Jp9ICttW1T5dVA==").unwrap(), 
+        authenticator_data: authenticator_data.clone(), 
+        client_data: ClientData::new("MTIz", "http://localhost:5173", true, None), 
+        client_data_json: Some(client_data_json.clone()),
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(credential.validate().is_ok());
+    assert!(credential.verify_cosmwasm(deps.api).is_ok());
+    assert!(credential.verify().is_ok());
+
+    // re-serializing the client data drops the unknown keys
+    credential.client_data_json = None;
+    assert!(credential.verify_cosmwasm(deps.api).is_err());
+
+    // raw data must match the expected values
+    credential.client_data_json = Some(client_data_json);
+    credential.client_data = ClientData::new("MTIz", "http://localhost:5173", false, None);
+    assert!(credential.validate().is_err());
+    assert!(credential.verify_cosmwasm(deps.api).is_err());
+    credential.client_data = ClientData::new("MTIz", "https://example.com", true, None);
+    assert!(credential.verify_cosmwasm(deps.api).is_err());
+
+    // escaped slashes are hashed as they are
+    let credential = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(pubkey), 
+        signature: Binary::from_base64("9dEGeVQUVSFhoMlOifPYMiMQeLJ6Hx7u9r/RltCJhPtlN9uIPiSGgpEAaOhSJnyna4CT6h2rtIgqJvx0GMMkiQ==").unwrap(), 
+        authenticator_data, 
+        client_data: ClientData::new(
+            "MTIz", 
+            "http://localhost:5173", 
+            false, 
+            Some(ClientDataOtherKeys::new(Some(OTHER_KEY.to_string())))
+        ), 
+        client_data_json: Some(Binary::from_base64("eyJ0eXBlIjoid2ViYXV0aG4uZ2V0IiwiY2hhbGxlbmdlIjoiTVRJeiIsIm9yaWdpbiI6Imh0dHA6Ly9sb2NhbGhvc3Q6NTE3MyIsImNyb3NzT3JpZ2luIjpmYWxzZSwib3RoZXJfa2V5c19jYW5fYmVfYWRkZWRfaGVyZSI6ImRvIG5vdCBjb21wYXJlIGNsaWVudERhdGFKU09OIGFnYWluc3QgYSB0ZW1wbGF0ZS4gU2VlIGh0dHBzOlwvXC9nb28uZ2xcL3lhYlBleCJ9").unwrap()),
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(credential.verify_cosmwasm(deps.api).is_ok());
+}
+
+
+
+#[test]
+fn passkey_der_signature() {
+    let deps = mock_dependencies();
+    let credential = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap()), 
+        // DER encoded and with high S as some authenticators return it
+        signature: Binary::from_base64("MEUCIAW99uTW63DznZkZrBvftWCIrZs5VuqNEOnIAYMtgP/yAiEA55smw2TtChbBhgnhJbga8fk6sBB6OWTvGaWNi1bn3Sw=").unwrap(), 
+        authenticator_data: Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap(), 
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None), 
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(credential.verify_cosmwasm(deps.as_ref().api).is_ok());
+    assert!(credential.verify().is_ok());
+}
+
+
+
+#[test]
+fn passkey_browser_style_client_data() {
+    let deps = mock_dependencies();
+    let deps = deps.as_ref();
+    let prefix = r#"{"type":"webauthn.get","challenge":"MTIz","origin":"http://localhost:5173","#;
+
+    // members laid out the way the browsers emit them and signed with the test key
+    let vectors = [
+        // Chrome style
+        (
+            r#""crossOrigin":false,"other_keys_can_be_added_here":"do not compare clientDataJSON against a template. See https://goo.gl/yabPex"}"#,
+            "nuxUnNnPf389trK19lIdjNh5gXeIPRAc9ioFYNfXy+wO11G/POeNxyS9yZE656XP8C2KoPFp4Qgy/tCpeJ8N5w==",
+            Some(ClientDataOtherKeys::default().with("other_keys_can_be_added_here", OTHER_KEY)),
+        ),
+        // Safari style inside a cross origin iframe
+        (
+            r#""crossOrigin":true,"topOrigin":"https://top.example"}"#,
+            "e9EH7rF2o5aCJCbTrBf9GpNVL7Hs1e+rRLiFL6LMthMX4mW/4zMcSqphOxDyrRgA1Ht534UELBU/LsazQL/KEQ==",
+            Some(ClientDataOtherKeys::default().with("topOrigin", "https://top.example")),
+        ),
+        // Firefox style without any extra members
+        (
+            r#""crossOrigin":false}"#,
+            "2YzVIwZgP6yASBZBVmO4abtL+ScDKOaQzHB9Cht9IX5Tp3Yg52swCv6rJNa0FI229HYQnik0RHc1G+p6+sCLsw==",
+            None,
+        ),
+        // nested values like the legacy `tokenBinding`
+        (
+            r#""crossOrigin":false,"tokenBinding":{"status":"present","id":"AQID"},"extra":[1,-2,true,null,"x"]}"#,
+            "i/loPLTEN+bkHwSYV3wKIQ2aYYqRefBaKOtaokxmea8KYVLMVneirjcHk5NVwwWSoBmXpv9/q2wvvUPG0knkEw==",
+            Some(ClientDataOtherKeys::default()
+                .with("tokenBinding", ClientDataOtherKeys::default().with("status", "present").with("id", "AQID"))
+                .with("extra", ClientDataValue::Array(vec![
+                    1i64.into(), (-2i64).into(), true.into(), ClientDataValue::Null, "x".into()
+                ]))
+            ),
+        ),
+    ];
+
+    for (rest, signature, other_keys) in vectors {
+        let json = format!("{prefix}{rest}");
+        let client_data = saa_common::from_json::<ClientData>(&json).unwrap();
+        assert_eq!(client_data.other_keys, other_keys);
+        assert_eq!(saa_common::to_json_string(&client_data).unwrap(), json);
+
+        // the same members passed through the payload are restored in the same order
+        let payload = PasskeyPayload { other_keys, origin: None, client_data_json: None, user_handle: None, authenticator_data: None };
+        let payload = saa_common::from_json::<PasskeyPayload>(to_json_binary(&payload).unwrap()).unwrap();
+        let client_data = ClientData::new("MTIz", "http://localhost:5173", client_data.cross_origin, payload.other_keys);
+        assert_eq!(saa_common::to_json_string(&client_data).unwrap(), json);
+
+        let credential = PasskeyCredential { 
+            id: String::default(),
+            pubkey: Some(Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap()), 
+            signature: Binary::from_base64(signature).unwrap(), 
+            authenticator_data: Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap(), 
+            client_data,
+            client_data_json: None,
+            user_handle: None,
+            authenticator_options: None,
+        };
+        assert!(credential.verify_cosmwasm(deps.api).is_ok());
+    }
+
+    // order of the members is part of the signed data
+    let reordered = format!(r#"{prefix}"crossOrigin":false,"extra":[1,-2,true,null,"x"],"tokenBinding":{{"status":"present","id":"AQID"}}}}"#);
+    let credential = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap()), 
+        signature: Binary::from_base64("i/loPLTEN+bkHwSYV3wKIQ2aYYqRefBaKOtaokxmea8KYVLMVneirjcHk5NVwwWSoBmXpv9/q2wvvUPG0knkEw==").unwrap(), 
+        authenticator_data: Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap(), 
+        client_data: saa_common::from_json(reordered).unwrap(),
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(credential.verify_cosmwasm(deps.api).is_err());
+
+    // regular members can't be injected and duplicates are rejected
+    let keys = ClientDataOtherKeys::default().with("origin", "https://evil.com");
+    assert!(to_json_binary(&ClientData::new("MTIz", "http://localhost:5173", false, Some(keys))).is_err());
+    assert!(saa_common::from_json::<ClientDataOtherKeys>(r#"{"a":"1","a":"2"}"#).is_err());
+
+    // schema describes the members as a map
+    let schema = saa_schema::schemars::schema_for!(PasskeyPayload);
+    let schema = saa_common::to_json_string(&schema).unwrap();
+    assert!(schema.contains(r#""type":"object","additionalProperties":true}"#));
+}
+
+
+
+#[test]
+fn passkey_extension_outputs() {
+    let deps = mock_dependencies();
+    // credProtect, hmac-secret, largeBlobKey, devicePubKey and an unknown extension
+    let authenticator_data = Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2OdAAAAAaVrY3JlZFByb3RlY3QDa2htYWMtc2VjcmV0WCAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eH2xsYXJnZUJsb2JLZXlYIAcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHbGRldmljZVB1YktleViMpmZhYWd1aWRQAAAAAAAAAAAAAAAAAAAAAGNkcGtYTaUBAgMmIAEhWCBRp1gIM4mOobGDy9c1CkCZB4xu8cHhjpcM12gwNfJefSJYIAEQUicSsLWnz/CBaFSGmEqU5oMe2sRuc2D6nYNKeoGhZXNjb3BlAGVub25jZUBjZm10ZG5vbmVnYXR0U3RtdKBxdGhpcmRQYXJ0eVBheW1lbnT1").unwrap();
+    let credential = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(Binary::from_base64("BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=").unwrap()), 
+        signature: Binary::from_base64("Dwgb8B6FbtfXCPxhJBUq9D+uTbYh3puki9nAq5REDCt2oP0b0s2HUOPyxK18RoUQtlpYuT4UjU3lwhXA+Gz2fA==").unwrap(), 
+        authenticator_data: authenticator_data.clone(), 
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None), 
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(credential.validate().is_ok());
+    assert!(credential.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    let outputs = credential.extension_outputs().unwrap().unwrap();
+    assert_eq!(outputs.cred_protect, Some(CredProtect::UserVerificationRequired));
+    assert!(outputs.cred_protect >= Some(CredProtect::UserVerificationOptionalWithCredentialIdList));
+    assert_eq!(outputs.hmac_secret, Some(HmacSecretOutput::Output(Binary::from((0u8..32).collect::<Vec<_>>()))));
+    assert_eq!(outputs.large_blob_key, Some(Binary::from([7u8; 32].to_vec())));
+    assert_eq!(outputs.unknown, vec!["thirdPartyPayment".to_string()]);
+
+    let device = outputs.device_pub_key.unwrap();
+    assert_eq!(device.aaguid, Binary::from([0u8; 16].to_vec()));
+    assert_eq!((device.scope, device.fmt.as_str(), device.nonce.len()), (0, "none", 0));
+    assert_eq!(device.att_stmt, Binary::from(vec![0xa0]));
+    assert!(matches!(PasskeyPublicKey::from_cose(&device.dpk), Ok(PasskeyPublicKey::Es256(_))));
+
+    // no extensions without the flag
+    let credential = PasskeyCredential {
+        authenticator_data: Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap(),
+        ..credential
+    };
+    assert_eq!(credential.extension_outputs().unwrap(), None);
+
+    // malformed outputs
+    let mut data = authenticator_data[..37].to_vec();
+    for outputs in [
+        vec![0xa1, 0x6b, b'c', b'r', b'e', b'd', b'P', b'r', b'o', b't', b'e', b'c', b't', 0x04],
+        vec![0xa1, 0x01, 0x01],
+        vec![0x80],
+    ] {
+        data.truncate(37);
+        data.extend(outputs);
+        let credential = PasskeyCredential { authenticator_data: Binary::from(data.clone()), ..credential.clone() };
+        assert!(credential.extension_outputs().is_err());
+    }
+}
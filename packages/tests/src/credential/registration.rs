@@ -0,0 +1,443 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{to_json_binary, AuthError, Binary, StorageError, Verifiable};
+use smart_account_auth::{
+    passkey_record, build_credential, find_credential_record, update_credential_info, msgs::{SignedDataMsg, AuthPayload},
+    types::{
+        PasskeyRegistration, RegistrationOptions, PasskeyInfo, PasskeyPublicKey, 
+        ClientData, PasskeyPayload, OriginPolicy, CredProtect
+    }, 
+    Credential, CredentialInfo, CredentialName, PasskeyCredential
+};
+
+
+const ID : &str = "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA";
+const CLIENT_DATA_JSON : &str = "eyJ0eXBlIjoid2ViYXV0aG4uY3JlYXRlIiwiY2hhbGxlbmdlIjoiY21WbmFYTjBaWEl0WTJoaGJHeGxibWRsIiwib3JpZ2luIjoiaHR0cDovL2xvY2FsaG9zdDo1MTczIiwiY3Jvc3NPcmlnaW4iOmZhbHNlfQ==";
+const NONE_ATTESTATION : &str = "o2NmbXRkbm9uZWdhdHRTdG10oGhhdXRoRGF0YVikSZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2NdAAAAAAAAAAAAAAAAAAAAAAAAAAAAIAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gpQECAyYgASFYIAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtIlggGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=";
+const SELF_ATTESTATION : &str = "o2NmbXRmcGFja2VkZ2F0dFN0bXSiY2FsZyZjc2lnWEgwRgIhAMaTvVkWZGHvSKuLug9HXjnxBagMo5p2O2360yKrkiudAiEAnNNzJbRr0QcGROnK1t4xsrVYvGs634g4BroWPK154whoYXV0aERhdGFYpEmWDeWIDoxodDQXD2R2YFuP5K65ooYyx5lc87qDHZdjXQAAAAAAAAAAAAAAAAAAAAAAAAAAACABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4fIKUBAgMmIAEhWCACF+YX8LZEOSgnj5aZnmmiOk8sFSvfbWzfZuW4AoLU7SJYIBlKfevLl3EtLdo8qFqodlpW9F/HWFmWUvKJfGUwbleU";
+const X5C_ATTESTATION : &str = "o2NmbXRmcGFja2VkZ2F0dFN0bXSjY2FsZyZjc2lnWEcwRQIhAO7Dl5b9SjGAg0WdAXCLuELpuZvnsutWmRAkpg9NN24BAiAy8W+JgB/qcDq+BrVgImyEiwF01erOcKwbVN13ULBUKWN4NWOBWQFvMIIBazCCARGgAwIBAgIBATAKBggqhkjOPQQDAjA/MRkwFwYDVQQDDBBUZXN0IEF0dGVzdGF0aW9uMSIwIAYDVQQLDBlBdXRoZW50aWNhdG9yIEF0dGVzdGF0aW9uMB4XDTI0MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowPzEZMBcGA1UEAwwQVGVzdCBBdHRlc3RhdGlvbjEiMCAGA1UECwwZQXV0aGVudGljYXRvciBBdHRlc3RhdGlvbjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABNZak5d8qj0bCBhS/1ennkZfFmBXcwS66tUF3TpIWJzzUBheiVNy32Ih6joTdVfkc/3bZ1XwW9UHw8Uz/OnJEoUwCgYIKoZIzj0EAwIDSAAwRQIhAMqnSIsAjGP05zXLY1LeG2JFWVLjyf0P2JHgoZvMYdVyAiBPk5nsVVJJmjeT7rtR/YlwRjeZozhOiBErx9N8WEl5XmhhdXRoRGF0YVikSZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2NdAAAAAAAAAAAAAAAAAAAAAAAAAAAAIAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gpQECAyYgASFYIAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtIlggGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=";
+const PUBKEY : &str = "BAIX5hfwtkQ5KCePlpmeaaI6TywVK99tbN9m5bgCgtTtGUp968uXcS0t2jyoWqh2Wlb0X8dYWZZS8ol8ZTBuV5Q=";
+const NONE_WITH_STATEMENT : &str = "o2NmbXRkbm9uZWdhdHRTdG10omNhbGcmY3NpZ1hHMEUCIGJBZys6WAmak+7jDxHjNPgdBRywRMdDa5t4iK0m9eoAAiEAjXZLdqGh32Xot9Y1P6xwV3LvCOpjhhqw0lYl4Qz1zmVoYXV0aERhdGFYpEmWDeWIDoxodDQXD2R2YFuP5K65ooYyx5lc87qDHZdjXQAAAAAAAAAAAAAAAAAAAAAAAAAAACABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4fIKUBAgMmIAEhWCACF+YX8LZEOSgnj5aZnmmiOk8sFSvfbWzfZuW4AoLU7SJYIBlKfevLl3EtLdo8qFqodlpW9F/HWFmWUvKJfGUwbleU";
+const ASSERTION_AUTH_DATA : &str = "SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==";
+
+
+fn registration(attestation: &str) -> PasskeyRegistration {
+    PasskeyRegistration {
+        id: ID.to_string(),
+        attestation_object: Binary::from_base64(attestation).unwrap(),
+        client_data_json: Binary::from_base64(CLIENT_DATA_JSON).unwrap(),
+        user_handle: None,
+    }
+}
+
+fn options() -> RegistrationOptions {
+    RegistrationOptions {
+        challenge: Binary::from(b"register-challenge".as_slice()),
+        origin: "http://localhost:5173".to_string(),
+        rp_id: "localhost".to_string(),
+    }
+}
+
+
+/// Payload of an assertion with the given authenticator data
+fn assertion_payload(authenticator_data: &str) -> Binary {
+    to_json_binary(&PasskeyPayload {
+        origin: None,
+        other_keys: None,
+        client_data_json: None,
+        user_handle: None,
+        authenticator_data: Some(Binary::from_base64(authenticator_data).unwrap()),
+    }).unwrap()
+}
+
+
+
+
+#[test]
+fn passkey_registration_formats() {
+    let deps = mock_dependencies();
+
+    for attestation in [NONE_ATTESTATION, SELF_ATTESTATION, X5C_ATTESTATION] {
+        let reg = registration(attestation);
+        let info = reg.verify(&options()).unwrap();
+        assert_eq!(info, reg.verify_cosmwasm(deps.as_ref().api, &options()).unwrap());
+
+        assert_eq!(info.pubkey.to_base64(), PUBKEY);
+        assert_eq!(info.authenticator_data.to_base64(), ASSERTION_AUTH_DATA);
+        assert_eq!(info.origin, "http://localhost:5173");
+        assert!(!info.cross_origin);
+        assert_eq!(info.origin_policy, Some(OriginPolicy {
+            origins: vec!["http://localhost:5173".to_string()],
+            rp_ids: vec!["localhost".to_string()],
+            ..Default::default()
+        }));
+    }
+}
+
+
+
+#[test]
+fn passkey_registration_checks() {
+    let reg = registration(X5C_ATTESTATION);
+
+    let mut opts = options();
+    opts.challenge = Binary::from(b"other-challenge".as_slice());
+    assert!(reg.verify(&opts).is_err());
+
+    let mut opts = options();
+    opts.origin = "https://example.com".to_string();
+    assert!(reg.verify(&opts).is_err());
+
+    let mut opts = options();
+    opts.rp_id = "example.com".to_string();
+    assert!(reg.verify(&opts).is_err());
+
+    let mut other = reg.clone();
+    other.id = "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyE".to_string();
+    assert!(other.verify(&options()).is_err());
+
+    // client data hash is covered by the attestation signature
+    let mut other = reg.clone();
+    let json = String::from_utf8(other.client_data_json.to_vec()).unwrap()
+        .replace("\"crossOrigin\":false", "\"crossOrigin\":true");
+    other.client_data_json = Binary::from(json.as_bytes());
+    assert!(other.verify(&options()).is_err());
+
+    // the none format must not carry a statement
+    assert!(registration(NONE_WITH_STATEMENT).verify(&options()).is_err());
+}
+
+
+
+#[test]
+fn passkey_registration_record() {
+    let deps = mock_dependencies();
+    let info = registration(SELF_ATTESTATION).verify(&options()).unwrap();
+    let record = passkey_record(ID.to_string(), &info).unwrap();
+    assert_eq!(record.1.name, CredentialName::Passkey);
+    assert_eq!(registration(SELF_ATTESTATION).extension_outputs().unwrap(), None);
+
+    // assertion signed by the registered passkey over the stored authenticator data
+    let msg = SignedDataMsg {
+        data: Binary::from_base64("MTIz").unwrap(),
+        signature: Binary::from_base64("bzAhKzyESLEVvEkPHgF8Am3P6Vx2xhxAPMVnndlggcc118qJm6VUVr6p1WwQzsPc4F2L4eeCfW0e+gYv0M4TSQ==").unwrap(),
+        payload: None,
+    };
+    assert!(matches!(build_credential(record.clone(), msg.clone(), None), Err(AuthError::MissingData(_))));
+    let cred = build_credential(record, msg, Some(assertion_payload(ASSERTION_AUTH_DATA))).unwrap();
+    assert!(matches!(cred, Credential::Passkey(_)));
+    // the origin policy of the ceremony isn't a part of the credential
+    assert_eq!(
+        cred.extension().unwrap().map(|e| saa_common::from_json::<PasskeyInfo>(e).unwrap()),
+        Some(PasskeyInfo { origin_policy: None, ..info })
+    );
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+}
+
+
+
+const ED25519_ATTESTATION : &str = "o2NmbXRmcGFja2VkZ2F0dFN0bXSiY2FsZydjc2lnWEA0fR9C6l1sy6SQ4T0m1IzrnYxsCMsQdcbfHMUj3hwonMyTXCpdb1ZxX+EHFw/S7r1alXUtKqVn0dyKmBmh/i8EaGF1dGhEYXRhWIFJlg3liA6MaHQ0Fw9kdmBbj+SuuaKGMseZXPO6gx2XY10AAAAAAAAAAAAAAAAAAAAAAAAAAAAgAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyCkAQEDJyAGIVgg6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iw=";
+const RSA_ATTESTATION : &str = "o2NmbXRkbm9uZWdhdHRTdG10oGhhdXRoRGF0YVkBZ0mWDeWIDoxodDQXD2R2YFuP5K65ooYyx5lc87qDHZdjXQAAAAAAAAAAAAAAAAAAAAAAAAAAACABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4fIKQBAwM5AQAgWQEArlTDdBKiIfjJUNq579mT/wySAiaSJIE+YNRNcdOzVl9onMRffAUV1P++6MRDhoMWdSMFIKSCS+W7wzO1g3Utik611//IBYTw5sH4evQC1+oLGWScv3Oh2xvSLi4LVoSLNgG8vKfVFIs3Q7P6WETaxryCI48r+rr/ZpEQOjb058PlzQaH6jRAta2Lnn4m/+uCRlts5CcF2e3f4+VH9JTvtZ7piGZs+Od7+Oh4ONCfCMnEIJ0uzS0uDWc2WnEfANvdy8kmPYk4q67rnlw1zrBAYc2TPfgeJ60MtNknx6NKnqb1qBYI5YV7ocHTVorloh8o0RUVZvk7gwY39LOeC74FlyFDAQAB";
+const PS256_COSE_KEY : &str = "pAEDAzgkIFkBAK5Uw3QSoiH4yVDaue/Zk/8MkgImkiSBPmDUTXHTs1ZfaJzEX3wFFdT/vujEQ4aDFnUjBSCkgkvlu8MztYN1LYpOtdf/yAWE8ObB+Hr0AtfqCxlknL9zodsb0i4uC1aEizYBvLyn1RSLN0Oz+lhE2sa8giOPK/q6/2aREDo29OfD5c0Gh+o0QLWti55+Jv/rgkZbbOQnBdnt3+PlR/SU77We6YhmbPjne/joeDjQnwjJxCCdLs0tLg1nNlpxHwDb3cvJJj2JOKuu655cNc6wQGHNkz34HietDLTZJ8ejSp6m9agWCOWFe6HB01aK5aIfKNEVFWb5O4MGN/Szngu+BZchQwEAAQ==";
+
+
+
+#[test]
+fn passkey_cose_algorithms() {
+    let deps = mock_dependencies();
+    let assertion = |info: &PasskeyInfo, signature: &str| PasskeyCredential {
+        id: ID.to_string(),
+        signature: Binary::from_base64(signature).unwrap(),
+        authenticator_data: info.authenticator_data.clone(),
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None),
+        client_data_json: None,
+        user_handle: None,
+        pubkey: Some(info.pubkey.clone()),
+        authenticator_options: None,
+    };
+
+    // EdDSA with self attestation
+    let info = registration(ED25519_ATTESTATION).verify(&options()).unwrap();
+    assert_eq!(info.pubkey.to_base64(), "pAEBAycgBiFYIOpKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIs");
+    let key = PasskeyPublicKey::decode(&info.pubkey).unwrap();
+    assert_eq!(key.algorithm(), -8);
+    let cred = assertion(&info, "C6GqCoXswSjOjh9RXsBEqx8uHLcUiCKQCotixx1ELxtHf/A3vMM2kYgfxPDJaQ9i/IGkKZZKtqqDccxadxtUDQ==");
+    assert!(cred.verify().is_ok());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    // RS256 with none attestation
+    let reg = registration(RSA_ATTESTATION);
+    let info = reg.verify_cosmwasm(deps.as_ref().api, &options()).unwrap();
+    assert_eq!(info, reg.verify(&options()).unwrap());
+    assert!(matches!(PasskeyPublicKey::decode(&info.pubkey).unwrap(), PasskeyPublicKey::Rs256 { .. }));
+    let rs256 = assertion(&info, "AaOuAcdlrlcadmjPJ/siM3feSUzVNxjaG6zkA+t4KbONGwF4S/mwPJAZ6/oDRzbtBTB5vmdsO7RY5B2jJYXiH3NOqzDZwyVFkkGOG3xCsHs6nhiKNi1GKZHVKU5E38f+bEu9w2Mj4XDg7O53J5s4yv6C72jiDY2LRy0Sq3FjKdknUM4NPggOra001C9zOuqgFn9s1vzY6cmp/5j2qrLHJj3zdHkoQjsihc2ez9CY9RmG4F2v8hXoY5Gh8Ir642WeuU2QlkiNWn8SHsmLp7micM0aAhSXjeJxMbYW2XAjbF9udT431GLzawxr6P5I3PRgRwRZU/Ske8kKcGIWwqlGww==");
+    assert!(rs256.verify().is_ok());
+    assert!(rs256.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    // PS256 with the same RSA key
+    let ps_info = PasskeyInfo { pubkey: Binary::from_base64(PS256_COSE_KEY).unwrap(), ..info.clone() };
+    let ps256 = assertion(&ps_info, "bGaM3gt3V55k7AA/FBdZpAqt7mKolOFCn/GFRUgg3F37qHvNRHdEL7vfufwgpFs88KMd7Qz7AXz0MVgbB3BbJOWI3fEXv07jRnrqNEolYGc9xRgnuRMEKivw8deinaCViEqAXooqwW9l/AubhqQZ5Ljr9h3BUx5WIjyhGqLSnJ+l3fLftHmDFxqkxRuPex24XYgdjmXMrUuFVNFadjqStVxplySJKVtoqN9Nqlpr83ThWD4F4KkFqRiTdiT4SKnuU/enqmOXHWIjsNKlyPxKcfgtZKzmmJAdNdCjfNt265YkZNjhkphVDOOCrKX7PnusX/1aFsB7JEmAbo4vpu5yJQ==");
+    assert!(ps256.verify().is_ok());
+    assert!(ps256.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    // padding schemes are not interchangeable
+    assert!(assertion(&ps_info, "AaOuAcdlrlcadmjPJ/siM3feSUzVNxjaG6zkA+t4KbONGwF4S/mwPJAZ6/oDRzbtBTB5vmdsO7RY5B2jJYXiH3NOqzDZwyVFkkGOG3xCsHs6nhiKNi1GKZHVKU5E38f+bEu9w2Mj4XDg7O53J5s4yv6C72jiDY2LRy0Sq3FjKdknUM4NPggOra001C9zOuqgFn9s1vzY6cmp/5j2qrLHJj3zdHkoQjsihc2ez9CY9RmG4F2v8hXoY5Gh8Ir642WeuU2QlkiNWn8SHsmLp7micM0aAhSXjeJxMbYW2XAjbF9udT431GLzawxr6P5I3PRgRwRZU/Ske8kKcGIWwqlGww==").verify().is_err());
+    assert!(assertion(&info, "bGaM3gt3V55k7AA/FBdZpAqt7mKolOFCn/GFRUgg3F37qHvNRHdEL7vfufwgpFs88KMd7Qz7AXz0MVgbB3BbJOWI3fEXv07jRnrqNEolYGc9xRgnuRMEKivw8deinaCViEqAXooqwW9l/AubhqQZ5Ljr9h3BUx5WIjyhGqLSnJ+l3fLftHmDFxqkxRuPex24XYgdjmXMrUuFVNFadjqStVxplySJKVtoqN9Nqlpr83ThWD4F4KkFqRiTdiT4SKnuU/enqmOXHWIjsNKlyPxKcfgtZKzmmJAdNdCjfNt265YkZNjhkphVDOOCrKX7PnusX/1aFsB7JEmAbo4vpu5yJQ==").verify().is_err());
+}
+
+
+
+#[test]
+fn passkey_origin_policy() {
+    let rp_id_hash = Binary::from_base64(ASSERTION_AUTH_DATA).unwrap()[..32].to_vec();
+    let policy = OriginPolicy {
+        origins: vec![
+            "https://*.example.com".to_string(),
+            "android:apk-key-hash:-sYXRdwJA3hvue3mKpYrOZ9zSPC7b4mbgzJmdZEDO5w".to_string(),
+        ],
+        rp_ids: vec!["localhost".to_string()],
+        cross_origin: false,
+        top_origins: vec![],
+    };
+    let check = |policy: &OriginPolicy, origin: &str| policy.check(origin, false, None, &rp_id_hash);
+
+    assert!(check(&policy, "https://app.example.com").is_ok());
+    assert!(check(&policy, "https://a.b.example.com").is_ok());
+    assert!(check(&policy, "android:apk-key-hash:-sYXRdwJA3hvue3mKpYrOZ9zSPC7b4mbgzJmdZEDO5w").is_ok());
+    for origin in [
+        "https://example.com",
+        "http://app.example.com",
+        "https://appexample.com",
+        "https://app.example.com.evil.com",
+        "https://evil.com/.example.com",
+        "https://.example.com",
+        "android:apk-key-hash:other",
+    ] {
+        assert_eq!(check(&policy, origin), Err(AuthError::OriginNotAllowed(origin.to_string())));
+    }
+    // relying party id must be in the list
+    let other_rp = OriginPolicy { rp_ids: vec!["example.com".to_string()], ..policy.clone() };
+    assert!(check(&other_rp, "https://app.example.com").is_err());
+
+    // cross origin requests and their top origins
+    let origin = "https://app.example.com";
+    assert!(matches!(policy.check(origin, true, None, &rp_id_hash), Err(AuthError::OriginNotAllowed(_))));
+    assert!(matches!(policy.check(origin, false, Some("https://top.com"), &rp_id_hash), Err(AuthError::OriginNotAllowed(_))));
+    let cross = OriginPolicy { cross_origin: true, top_origins: vec!["https://top.com".to_string()], ..policy.clone() };
+    assert!(cross.check(origin, true, None, &rp_id_hash).is_ok());
+    assert!(cross.check(origin, true, Some("https://top.com"), &rp_id_hash).is_ok());
+    assert_eq!(
+        cross.check(origin, true, Some("https://evil.com"), &rp_id_hash),
+        Err(AuthError::OriginNotAllowed("https://evil.com".to_string()))
+    );
+    // android apps can't be embedded
+    assert!(cross.check("android:apk-key-hash:-sYXRdwJA3hvue3mKpYrOZ9zSPC7b4mbgzJmdZEDO5w", true, None, &rp_id_hash).is_err());
+
+    // payload can't switch to an origin that isn't allowed for the stored passkey
+    let info = registration(SELF_ATTESTATION).verify(&options()).unwrap();
+    let msg = SignedDataMsg {
+        data: Binary::from_base64("MTIz").unwrap(),
+        signature: Binary::from_base64("bzAhKzyESLEVvEkPHgF8Am3P6Vx2xhxAPMVnndlggcc118qJm6VUVr6p1WwQzsPc4F2L4eeCfW0e+gYv0M4TSQ==").unwrap(),
+        payload: None,
+    };
+    let payload = |origin: &str| Some(to_json_binary(&PasskeyPayload {
+        origin: Some(origin.to_string()),
+        other_keys: None,
+        client_data_json: None,
+        user_handle: None,
+        authenticator_data: Some(Binary::from_base64(ASSERTION_AUTH_DATA).unwrap()),
+    }).unwrap());
+    let record = passkey_record(ID.to_string(), &info).unwrap();
+    assert!(build_credential(record.clone(), msg.clone(), payload("http://localhost:5173")).is_ok());
+    assert_eq!(
+        build_credential(record, msg.clone(), payload("https://app.example.com")).err(),
+        Some(AuthError::OriginNotAllowed("https://app.example.com".to_string()))
+    );
+    let info = PasskeyInfo { origin_policy: Some(policy), ..info };
+    let record = passkey_record(ID.to_string(), &info).unwrap();
+    assert!(build_credential(record.clone(), msg.clone(), payload("https://app.example.com")).is_ok());
+    assert!(build_credential(record, msg, payload("https://example.com")).is_err());
+}
+
+
+
+#[test]
+fn passkey_user_handle_lookup() {
+    let deps = mock_dependencies();
+    let mut registration = registration(SELF_ATTESTATION);
+    registration.user_handle = Some("dXNlci0x".to_string());
+    let info = registration.verify(&options()).unwrap();
+    assert_eq!(info.user_handle, Some("dXNlci0x".to_string()));
+
+    let other = PasskeyInfo { user_handle: Some("dXNlci0y".to_string()), ..info.clone() };
+    let records = vec![
+        (PUBKEY.to_string(), smart_account_auth::CredentialInfo { 
+            name: CredentialName::Secp256r1, hrp: None, extension: None 
+        }),
+        passkey_record("b3RoZXI".to_string(), &other).unwrap(),
+        passkey_record(ID.to_string(), &info).unwrap(),
+    ];
+    let passkey_payload = |user_handle: &str| PasskeyPayload {
+        origin: None,
+        other_keys: None,
+        client_data_json: None,
+        user_handle: Some(user_handle.to_string()),
+        authenticator_data: Some(Binary::from_base64(ASSERTION_AUTH_DATA).unwrap()),
+    };
+    let auth_payload = |credential_id: Option<&str>, user_handle: &str| AuthPayload {
+        hrp: None,
+        credential_id: credential_id.map(|id| id.to_string()),
+        extension: Some(to_json_binary(&passkey_payload(user_handle)).unwrap()),
+    };
+
+    // usernameless login where only the user handle is known
+    let payload = auth_payload(None, "dXNlci0x");
+    let record = find_credential_record(records.clone(), Some(&payload)).unwrap();
+    assert_eq!(record.0, ID);
+    assert_eq!(
+        find_credential_record(records.clone(), Some(&auth_payload(None, "dXNlci0z"))).err(),
+        Some(AuthError::Storage(StorageError::NotFound))
+    );
+    // credential id takes precedence
+    let by_id = find_credential_record(records.clone(), Some(&auth_payload(Some("b3RoZXI"), "dXNlci0x"))).unwrap();
+    assert_eq!(by_id.0, "b3RoZXI");
+    assert!(find_credential_record(records.clone(), None).is_err());
+    assert!(find_credential_record(
+        records.clone(), 
+        Some(&AuthPayload { hrp: None, credential_id: None, extension: None })
+    ).is_err());
+    // handle shared by several passkeys is ambiguous
+    let mut shared = records.clone();
+    shared.push(passkey_record("c2hhcmVk".to_string(), &info).unwrap());
+    assert!(find_credential_record(shared, Some(&payload)).is_err());
+
+    // returned user handle must match the stored one
+    let msg = SignedDataMsg {
+        data: Binary::from_base64("MTIz").unwrap(),
+        signature: Binary::from_base64("bzAhKzyESLEVvEkPHgF8Am3P6Vx2xhxAPMVnndlggcc118qJm6VUVr6p1WwQzsPc4F2L4eeCfW0e+gYv0M4TSQ==").unwrap(),
+        payload: Some(payload.clone()),
+    };
+    let cred = build_credential(record.clone(), msg.clone(), payload.extension.clone()).unwrap();
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+    let wrong = Some(to_json_binary(&passkey_payload("dXNlci0y")).unwrap());
+    assert!(matches!(build_credential(record, msg.clone(), wrong.clone()), Err(AuthError::Unauthorized(_))));
+    assert!(build_credential(by_id, msg, wrong).is_ok());
+}
+
+
+
+#[test]
+fn passkey_signature_counter() {
+    let deps = mock_dependencies();
+    let api = deps.as_ref().api;
+    let info = registration(SELF_ATTESTATION).verify(&options()).unwrap();
+    assert_eq!(info.sign_count, Some(0));
+    let record = passkey_record(ID.to_string(), &info).unwrap();
+
+    let assertion = |auth_data: &str, signature: &str| (
+        SignedDataMsg {
+            data: Binary::from_base64("MTIz").unwrap(),
+            signature: Binary::from_base64(signature).unwrap(),
+            payload: None,
+        },
+        Some(assertion_payload(auth_data))
+    );
+    let verify = |info: &CredentialInfo, (msg, payload): (SignedDataMsg, Option<Binary>)| {
+        let cred = build_credential((ID.to_string(), info.clone()), msg, payload)?;
+        cred.verify_cosmwasm(api)?;
+        update_credential_info(info.clone(), &cred)
+    };
+    let stored = |info: &CredentialInfo| saa_common::from_json::<PasskeyInfo>(
+        info.extension.as_ref().unwrap()
+    ).unwrap();
+    let stored_count = |info: &CredentialInfo| stored(info).sign_count;
+
+    // first assertion stores its counter
+    let first = || assertion(
+        "SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAABQ==",
+        "NYZyRb5za17L3WpNwEzwi5/cj6Bh/gyMxfO3TroH7G1OFQc/CXtgnxhg+XebbERGkiXjN00Z2/RKgultfu86rA=="
+    );
+    let info = verify(&record.1, first()).unwrap();
+    assert_eq!(stored_count(&info), Some(5));
+
+    // replaying it fails while the next one passes and moves the counter further
+    assert!(matches!(verify(&info, first()), Err(AuthError::Unauthorized(_))));
+    let second = || assertion(
+        "SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAABg==",
+        "v2OsbhvHFn61Pdbx1NmPJyjrey5bqXcS35zUNGYSTCV0RybjJ3CdSxQ3VxnpLicy3YIe/u934SzNb+AUqEbxdQ=="
+    );
+    let info = verify(&info, second()).unwrap();
+    assert_eq!(stored_count(&info), Some(6));
+    assert!(matches!(verify(&info, second()), Err(AuthError::Unauthorized(_))));
+
+    // credentials passed directly are checked against the stored counter when updating the info
+    let (msg, payload) = second();
+    let direct = match build_credential(record.clone(), msg, payload).unwrap() {
+        Credential::Passkey(c) => PasskeyCredential { authenticator_options: None, ..c },
+        _ => unreachable!()
+    };
+    assert!(direct.verify_cosmwasm(api).is_ok());
+    let direct_cred = Credential::Passkey(direct.clone());
+    assert!(matches!(update_credential_info(info.clone(), &direct_cred), Err(AuthError::Unauthorized(_))));
+    assert_eq!(stored_count(&update_credential_info(record.1.clone(), &direct_cred).unwrap()), Some(6));
+
+    // correctly signed assertion for another relying party
+    let (other_auth_data, other_signature) = (
+        "o3mm9u6vuaVeN4wRgDTidR5oL6ufLTCrE9ISVYbOGUcdAAAABw==",
+        "k2ioPJ/3ETxq/MdWh/kjV5kOh659bOEkuq4hnBeUlyg/2KIwLaUvse9nzU3YBNuyPP84IX9t2A5zdt6hA/8F3w=="
+    );
+    assert!(verify(&info, assertion(other_auth_data, other_signature)).is_err());
+    // even when the stored policy lists no relying party ids
+    let legacy = PasskeyInfo { origin_policy: None, ..stored(&info) };
+    let legacy = passkey_record(ID.to_string(), &legacy).unwrap().1;
+    assert!(verify(&legacy, assertion(other_auth_data, other_signature)).is_err());
+    let direct = Credential::Passkey(PasskeyCredential {
+        authenticator_data: Binary::from_base64(other_auth_data).unwrap(),
+        signature: Binary::from_base64(other_signature).unwrap(),
+        ..direct
+    });
+    assert!(direct.verify_cosmwasm(api).is_ok());
+    assert!(update_credential_info(legacy, &direct).is_err());
+
+    // correctly signed assertion without the user presence flag
+    let absent = assertion(
+        "SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2McAAAABQ==",
+        "bmHkLigLeyyU8Ask4WpqZ5svnfzfesCFaqNgkUiJLjBzZvpDR5dDHd3ZoMFTCsE5nKpA40syn8uF9ZdAoYSwvQ=="
+    );
+    assert_eq!(
+        verify(&record.1, absent).err(),
+        Some(AuthError::generic("User presence flag is not set"))
+    );
+}
+
+
+
+#[test]
+fn passkey_assertion_extension_outputs() {
+    let deps = mock_dependencies();
+    let info = registration(SELF_ATTESTATION).verify(&options()).unwrap();
+    let record = passkey_record(ID.to_string(), &info).unwrap();
+
+    // registered without extensions but the assertion carries `credProtect`
+    let msg = SignedDataMsg {
+        data: Binary::from_base64("MTIz").unwrap(),
+        signature: Binary::from_base64("MqR1xgVB9H0vC3++4Gp4dZre6ScTCbvKdwfAqlY+5g0m6MKmOTcSngjiCeVM6EZjhXDsFN1denztOlCdiSWYtw==").unwrap(),
+        payload: None,
+    };
+    let payload = Some(to_json_binary(&PasskeyPayload {
+        origin: None,
+        other_keys: None,
+        client_data_json: None,
+        user_handle: None,
+        authenticator_data: Some(Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2OdAAAABqFrY3JlZFByb3RlY3QC").unwrap()),
+    }).unwrap());
+    let credential = match build_credential(record.clone(), msg.clone(), payload).unwrap() {
+        Credential::Passkey(c) => c,
+        _ => unreachable!()
+    };
+    assert!(credential.verify_cosmwasm(deps.as_ref().api).is_ok());
+    let outputs = credential.extension_outputs().unwrap().unwrap();
+    assert_eq!(outputs.cred_protect, Some(CredProtect::UserVerificationOptionalWithCredentialIdList));
+
+    // the stored authenticator data can't stand in for the one of the assertion
+    assert!(matches!(build_credential(record, msg, None), Err(AuthError::MissingData(_))));
+}
@@ -0,0 +1,52 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{Binary, Verifiable};
+use smart_account_auth::{StellarSignMessage, utils::stellar::{decode_account_id, encode_account_id}};
+use crate::utils::SIGN_MESSAGE_BASE64;
+
+
+#[test]
+fn stellar_sep53_vector() {
+    let deps = mock_dependencies();
+    // test vector from the SEP-53 specification
+    let cred = StellarSignMessage {
+        signer: "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L".to_string(),
+        message: Binary::from(b"Hello, World!".to_vec()),
+        signature: Binary::from_base64(
+            "fO5dbYhXUhBMhe6kId/cuVq/AfEnHRHEvsP8vXh03M1uLpi5e46yO2Q8rEBzu3feXQewcQE5GArp88u6ePK6BA=="
+        ).unwrap(),
+    };
+    assert!(cred.validate().is_ok());
+    assert!(cred.verify().is_ok());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+}
+
+
+#[test]
+fn stellar_sign_message_verifiable() {
+    let deps = mock_dependencies();
+    let cred = StellarSignMessage {
+        signer: "GCXT2IBGJ6OCN3YILNOOKN7UC7KCIA32BFR2MODP63IFBZN7O43RIAXB".to_string(),
+        message: Binary::from_base64(SIGN_MESSAGE_BASE64).unwrap(),
+        signature: Binary::from_base64(
+            "3kpqWxeBIqHJyEM3nSIvrhb24W21t/VTm0hCoWZQbHMmsr/xXoP9zM0Ztpu79kXprwBzjG0TQ36/gZVIFC1GBA=="
+        ).unwrap(),
+    };
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    let mut other = cred.clone();
+    other.signer = "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L".to_string();
+    assert!(other.verify().is_err());
+}
+
+
+#[test]
+fn stellar_strkeys() {
+    let address = "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L";
+    let pubkey = decode_account_id(address).unwrap();
+    assert_eq!(encode_account_id(&pubkey), address);
+
+    // broken checksum
+    assert!(decode_account_id("GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7M").is_err());
+    // secret seed instead of an account id
+    assert!(decode_account_id("SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW").is_err());
+}
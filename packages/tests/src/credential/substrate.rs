@@ -0,0 +1,89 @@
+use saa_common::{substrate::default::EnvAccess, Binary, Verifiable};
+use smart_account_auth::{
+    crypto::InkCrypto, types::{ClientData, ClientDataOtherKeys, ClientDataValue, Ed25519Mode},
+    Credential, CredentialData, Ed25519, EthPersonalSign, PasskeyCredential, Secp256k1,
+};
+
+
+const MESSAGE : &[u8] = b"der encoded signature";
+
+
+
+#[test]
+fn ink_off_chain_verification() {
+    let api = InkCrypto(EnvAccess::default());
+
+    let eth = EthPersonalSign {
+        signer: "0xac03048da6065e584d52007e22c69174cdf2b91a".to_string(),
+        message: Binary::from(r#"{"chain_id":"elgafar-1","contract_address":"stars1gjgfp9wps9c0r3uqhr0xxfgu02rnzcy6gngvwpm7a78j7ykfqquqr2fuj4","messages":["Create TBA account"],"nonce":"0"}"#.as_bytes()),
+        signature: Binary::from_base64("a/lQuaTyhcTEeRA2XFTPxoDSIdS3yUUH1VSKOm2zz5EURfheGzzLgXea6QAalswOM2njnUzblqIGiOC0P+j2rhw=").unwrap(),
+    };
+    assert!(eth.verify_with(&api).is_ok());
+    assert!(EthPersonalSign { message: Binary::from(MESSAGE), ..eth.clone() }.verify_with(&api).is_err());
+
+    let k1 = |signature: &str| Secp256k1 {
+        pubkey: Binary::from_base64("AjxyrdtP3wmvlPDJTX/pKjhqfnDPih2FkWOGuyU1x7Gx").unwrap(),
+        message: Binary::from(MESSAGE),
+        signature: Binary::from_base64(signature).unwrap(),
+        hrp: None,
+        hash: None,
+        high_s: None,
+    };
+    let secp256k1 = k1("IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm8ZlF9hh2fkSk8TpSWDI/xX/FV4j1JaT4y61tZTiVcpSg==");
+    assert!(secp256k1.verify_with(&api).is_ok());
+    // same signature with `s` replaced by `n - s`
+    assert!(k1("IMJYH/vzErpEncjRf+lhfvV/dqrRhSX09/8xzwoIxm/ma6CeeJgbtbDsWtp83AOmvllkV1zuUK8E+4g5Rt8X9w==").verify_with(&api).is_ok());
+    // zero `r` is rejected before reaching the environment
+    assert!(k1(&Binary::from([0u8; 64].as_slice()).to_base64()).verify_with(&api).is_err());
+    assert!(k1("WMo/etkrDWRbBJgoRDrFaidIsYiFYYabcquI8XFFwWNuj+/Ujvpa5v3oiWYwnfeR9mW+oSRhQH5mW/SSREiDzw==").verify_with(&api).is_err());
+
+    // RFC 8032 test 2
+    let ed25519 = Ed25519 {
+        pubkey: Binary::from_base64("PUAXw+hDiVqStwqnTRt+vJyYLM8uxJaMwM1V8Sr0Zgw=").unwrap(),
+        message: Binary::from(b"\x72"),
+        signature: Binary::from_base64("kqAJqfDUyrhyDoILX2QlQKKye1QWUD+Ps3YiI+vbadoIWsHkPhWZbkWPNhPQ8R2MOHsurrQwKu6wDSkWErsMAA==").unwrap(),
+        mode: Some(Ed25519Mode::Pure),
+        hash: None,
+    };
+    assert!(ed25519.verify_with(&api).is_ok());
+    assert!(Ed25519 { mode: None, ..ed25519.clone() }.verify_with(&api).is_err());
+
+    let passkey = PasskeyCredential { 
+        id: String::default(),
+        pubkey: Some(Binary::from_base64("BOirsl/nNsTWj3O5Qfseo9qZfs0uakJ6I97JLDZSbmeYk6nwkjIHM7UKp1DD/UnmurwUMFoqRIkO7sqsRFg8eUU=").unwrap()), 
+        signature: Binary::from_base64("z+0mm8OPyXrkeowj0P9COBElCZqmd7L6oJS2WDVarM6hoeIz0v4pnvQ8FAmUQthbwhfa03WoUUXVvzufNNw+nA==").unwrap(), 
+        authenticator_data: Binary::from_base64("SZYN5YgOjGh0NBcPZHZgW4/krrmihjLHmVzzuoMdl2MdAAAAAA==").unwrap(), 
+        client_data: ClientData::new("MTIz", "http://localhost:5173", false, None), 
+        client_data_json: None,
+        user_handle: None,
+        authenticator_options: None,
+    };
+    assert!(passkey.verify_with(&api).is_ok());
+
+    let data = CredentialData {
+        credentials: vec![
+            Credential::EthPersonalSign(eth),
+            Credential::Secp256k1(secp256k1),
+            Credential::Ed25519(ed25519),
+            Credential::Passkey(passkey),
+        ],
+        use_native: None,
+        primary_index: None,
+    };
+    assert!(data.verify_ink(EnvAccess::default()).is_ok());
+}
+
+
+
+#[test]
+fn client_data_other_keys_scale_codec() {
+    use saa_schema::scale::{Decode, Encode};
+    let other_keys = ClientDataOtherKeys::from_iter([
+        ("topOrigin", ClientDataValue::from("https://top.example")),
+        ("tokenBinding", ClientDataOtherKeys::from_iter([("status", "present")]).into()),
+        ("extra", ClientDataValue::from(vec![ClientDataValue::Null, 1i64.into(), true.into()])),
+    ]);
+    let encoded = other_keys.encode();
+    assert_eq!(ClientDataOtherKeys::decode(&mut encoded.as_slice()).unwrap(), other_keys);
+    assert!(ClientDataOtherKeys::decode(&mut &encoded[..encoded.len() - 1]).is_err());
+}
@@ -0,0 +1,81 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{Binary, Verifiable};
+use smart_account_auth::{TezosSignPayload, utils::tezos::{decode_signature, pack_string, unpack_string}};
+use crate::utils::SIGN_MESSAGE_PLAIN;
+
+
+fn get_tezos(signer: &str, pubkey: &str, signature: &str) -> TezosSignPayload {
+    TezosSignPayload {
+        signer: signer.to_string(),
+        pubkey: pubkey.to_string(),
+        message: Binary::from(SIGN_MESSAGE_PLAIN.as_bytes()),
+        signature: Binary::from_base64(signature).unwrap(),
+    }
+}
+
+fn all_accounts() -> Vec<TezosSignPayload> {
+    vec![
+        get_tezos(
+            "tz1PyoGrKdVx6DuWhE5CJEbZgjBRRq75zmdA",
+            "edpkuUa1j4PisYBK7qVGuQ5QZv6PukSuAWB8pWxsLK74vsywPW2KFw",
+            "B3n9UFXSnunM5/qhMuCM2xvGKd9h4q/0/8eocGSYDOa9AUgdFBRmNgXd7qxOEu77QSZ6RuUnK61D6s1NXU1xAA=="
+        ),
+        get_tezos(
+            "tz2XAz3DMGgRVpmGaHHqsz9FrM3HkmFVk3TZ",
+            "sppk7ZNaeRBf1BVfuVvfGBfQhFD61EjGo3DgtZb2PSXznNyKfiBxLyU",
+            "zP6tq7AQI2rfYxaYy7X/eVgd/OEpZjWT3lQ1GF7JOcEWWQ7YFKqrzf3exIB4xdkJIpR55/dtujW3ItbQ1rPEHA=="
+        ),
+        get_tezos(
+            "tz3isPznA9kbtZyf8CAJPNoLGbpbi3myMgPP",
+            "p2pk66UqrCiSpVtK6Sb3KG1YGJy5M1dCSrag2feVPmXGaXG1Goryk23",
+            "B/vHxvnfRLVXaoZy1gbdffsYrLZoy2MEUXnahP+pHcBWMCHQeWx3aN+FCwVzAL7iY809t48rQ2o5m7x9FixhhQ=="
+        ),
+    ]
+}
+
+
+#[test]
+fn tezos_all_curves_verifiable() {
+    let deps = mock_dependencies();
+    for cred in all_accounts() {
+        assert!(cred.validate().is_ok(), "{} is invalid", cred.signer);
+        assert!(cred.verify().is_ok(), "{} native verification failed", cred.signer);
+        assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok(), "{} api verification failed", cred.signer);
+    }
+}
+
+
+#[test]
+fn tezos_mismatches() {
+    let deps = mock_dependencies();
+    let accounts = all_accounts();
+
+    // key of another account
+    let mut cred = accounts[0].clone();
+    cred.pubkey = accounts[1].pubkey.clone();
+    assert!(cred.validate().is_err());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err());
+
+    // message outside of printable ASCII
+    let mut cred = accounts[1].clone();
+    cred.message = Binary::from("ünïcode".as_bytes());
+    assert!(cred.validate().is_err());
+
+    let mut cred = accounts[2].clone();
+    cred.message = Binary::from(b"tampered".to_vec());
+    assert!(cred.verify().is_err());
+}
+
+
+#[test]
+fn tezos_encodings() {
+    let packed = pack_string(SIGN_MESSAGE_PLAIN.as_bytes());
+    assert_eq!(&packed[..2], &[0x05, 0x01]);
+    assert_eq!(unpack_string(&packed).unwrap(), SIGN_MESSAGE_PLAIN.as_bytes());
+    assert!(unpack_string(&packed[..packed.len() - 1]).is_err());
+
+    let sig = decode_signature(
+        "edsigtYnVeYR3k1nzdvHindRemMDhJSrYm41wzn5WXnzbGLAZ8g9ZjzKdhSV4EwVHH5saHUczEiv3zt6hmYhqiCbWEBQF7E9Rms"
+    ).unwrap();
+    assert_eq!(sig, all_accounts()[0].signature.to_vec());
+}
@@ -0,0 +1,99 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::Timestamp;
+use saa_common::{to_json_binary, AuthError, Binary, Verifiable};
+use smart_account_auth::{
+    build_credential, build_credential_env, msgs::SignedDataMsg,
+    types::{TonProofChecks, TonProofInfo, TonProofPayload},
+    Credential, CredentialInfo, CredentialName, TonProof
+};
+use crate::utils::SIGN_MESSAGE_BASE64;
+
+
+const DOMAIN : &str = "app.megarock.xyz";
+const TIMESTAMP : u64 = 1700000000;
+
+
+fn get_ton_proof() -> TonProof {
+    TonProof {
+        address: "0:e8d44050873dba865aa7c170ab4cce64d90839a34dcfd6cf71d14e0205443b1b".to_string(),
+        pubkey: Binary::from_base64("VCgh6VKd0a0kO/3/XGQKZRlLSBaYemUN6GHXzp5BemM=").unwrap(),
+        domain: DOMAIN.to_string(),
+        timestamp: TIMESTAMP,
+        message: Binary::from_base64(SIGN_MESSAGE_BASE64).unwrap(),
+        signature: Binary::from_base64(
+            "/ub+Iyh6zyWK0d2xXojymXKZceDHrkZh5vo+gTtU/ClXPEF+HUD2JJlZpqYATlGVUXkfC2Smvxx8bops1XCzBA=="
+        ).unwrap(),
+        checks: Some(TonProofChecks {
+            domains: vec![DOMAIN.to_string()],
+            max_age: 300,
+            now: TIMESTAMP + 60,
+        }),
+    }
+}
+
+
+#[test]
+fn ton_proof_verifiable() {
+    let deps = mock_dependencies();
+    let cred = get_ton_proof();
+    assert!(cred.validate().is_ok());
+    assert!(cred.verify().is_ok());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    let mut cred = get_ton_proof();
+    cred.domain = "evil.xyz".to_string();
+    cred.checks.as_mut().unwrap().domains.push(cred.domain.clone());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err(), "Domain is a part of the signed message");
+
+    let mut cred = get_ton_proof();
+    cred.address = "-1:e8d44050873dba865aa7c170ab4cce64d90839a34dcfd6cf71d14e0205443b1b".to_string();
+    assert!(cred.verify().is_err(), "Workchain is a part of the signed message");
+}
+
+
+#[test]
+fn ton_proof_domain_and_time() {
+    let deps = mock_dependencies();
+    let api = deps.as_ref().api;
+    let with_checks = |domains: &[&str], max_age: u64, now: u64| TonProof {
+        checks: Some(TonProofChecks {
+            domains: domains.iter().map(|d| d.to_string()).collect(),
+            max_age,
+            now,
+        }),
+        ..get_ton_proof()
+    };
+    assert!(with_checks(&[DOMAIN], 300, TIMESTAMP + 300).verify_cosmwasm(api).is_ok());
+    assert!(with_checks(&[DOMAIN], 60, TIMESTAMP - 30).verify().is_ok(), "Small clock skew is fine");
+
+    // correctly signed proofs for another app or too old are rejected
+    assert!(matches!(with_checks(&["other.xyz"], 300, TIMESTAMP).verify_cosmwasm(api), Err(AuthError::Unauthorized(_))));
+    assert!(matches!(with_checks(&[DOMAIN], 300, TIMESTAMP + 301).verify(), Err(AuthError::Unauthorized(_))));
+    let unchecked = TonProof { checks: None, ..get_ton_proof() };
+    assert!(unchecked.verify_cosmwasm(api).is_err());
+
+    // stored requirements are enforced when building the credential from a signed message
+    let proof = get_ton_proof();
+    let info = TonProofInfo { pubkey: proof.pubkey.clone(), domains: vec![DOMAIN.to_string()], max_age: 300 };
+    assert_eq!(Credential::TonProof(proof.clone()).extension().unwrap(), Some(to_json_binary(&info).unwrap()));
+    let record = (proof.address.clone(), CredentialInfo {
+        name: CredentialName::TonProof,
+        hrp: None,
+        extension: Some(to_json_binary(&info).unwrap()),
+    });
+    let msg = SignedDataMsg { data: proof.message.clone(), signature: proof.signature.clone(), payload: None };
+    let payload = |domain: &str| Some(to_json_binary(&TonProofPayload { domain: domain.to_string(), timestamp: TIMESTAMP }).unwrap());
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(TIMESTAMP + 60);
+    let cred = build_credential_env(record.clone(), msg.clone(), payload(DOMAIN), &env).unwrap();
+    assert!(cred.verify_cosmwasm(api).is_ok());
+    assert!(build_credential(record.clone(), msg.clone(), payload(DOMAIN)).is_err(), "Block time is required");
+
+    let cred = build_credential_env(record.clone(), msg.clone(), payload("evil.xyz"), &env).unwrap();
+    assert!(matches!(cred.verify_cosmwasm(api), Err(AuthError::Unauthorized(_))));
+
+    env.block.time = Timestamp::from_seconds(TIMESTAMP + 301);
+    let cred = build_credential_env(record, msg, payload(DOMAIN), &env).unwrap();
+    assert!(matches!(cred.verify_cosmwasm(api), Err(AuthError::Unauthorized(_))), "Proof is too old");
+}
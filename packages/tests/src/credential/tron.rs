@@ -0,0 +1,51 @@
+use cosmwasm_std::testing::mock_dependencies;
+use saa_common::{AuthError, Binary, Verifiable};
+use smart_account_auth::{TronSignMessage, utils::tron::{address_to_bytes, bytes_to_address}};
+use crate::utils::SIGN_MESSAGE_BASE64;
+
+
+const SIGNER : &str = "TGkWdpawVNfeset3P6uTBbLaPY7nZVZvXY";
+
+
+fn get_tron() -> TronSignMessage {
+    TronSignMessage {
+        signer: SIGNER.to_string(),
+        message: Binary::from_base64(SIGN_MESSAGE_BASE64).unwrap(),
+        signature: Binary::from_base64(
+            "AwrA4XnIjVnpayRZN5v3U7alCsxuQ12nouonIfWlbYJJrBuMpv3imEHUB7QAdHzefWNyGtIHenOoZg6aSdLMXxw="
+        ).unwrap(),
+    }
+}
+
+
+#[test]
+fn tron_sign_message_verifiable() {
+    let deps = mock_dependencies();
+    let cred = get_tron();
+    assert!(cred.validate().is_ok());
+    assert!(cred.verify().is_ok());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_ok());
+
+    let mut cred = get_tron();
+    cred.message = Binary::from(b"another message".to_vec());
+    assert!(cred.verify_cosmwasm(deps.as_ref().api).is_err());
+
+    // short signatures are rejected without being validated first
+    let cred = TronSignMessage { signature: Binary::from(&get_tron().signature[..64]), ..get_tron() };
+    assert!(matches!(cred.verify(), Err(AuthError::InvalidLength(..))));
+    assert!(matches!(cred.verify_cosmwasm(deps.as_ref().api), Err(AuthError::InvalidLength(..))));
+}
+
+
+#[test]
+fn tron_addresses() {
+    let bytes = address_to_bytes(SIGNER).unwrap();
+    assert_eq!(bytes_to_address(&bytes), SIGNER);
+
+    // broken checksum
+    assert!(address_to_bytes("TGkWdpawVNfeset3P6uTBbLaPY7nZVZvXZ").is_err());
+    // bitcoin address with a valid checksum but not a TRON prefix
+    let mut cred = get_tron();
+    cred.signer = "1BoatSLRHtKNngkdXEeobR76b53LETtpyT".to_string();
+    assert!(cred.validate().is_err());
+}